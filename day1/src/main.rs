@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use utils::test_solutions;
+use utils::{test_solutions, Cursor};
 
 fn main() {
     test_solutions(1, &first_part, Some(11), &second_part, Some(31));
@@ -7,19 +7,21 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
+/// Parses the input into its left and right columns.
+fn parse_columns(input: &str) -> (Vec<i32>, Vec<i32>) {
+    let mut cursor = Cursor::from(input);
+    let mut columns = (vec![], vec![]);
+    while cursor.skip_until_digit() {
+        columns.0.push(cursor.next_unsigned().unwrap());
+        columns.1.push(cursor.next_unsigned().unwrap());
+    }
+    columns
+}
+
 /* ------------------- Solutions ------------------- */
 
 fn first_part(input: &str) -> i32 {
-    let mut first_col: Vec<i32> = Vec::new();
-    let mut second_col: Vec<i32> = Vec::new();
-
-    for line in input.lines() {
-        let mut split = line
-            .split_whitespace()
-            .map(|num| num.parse::<i32>().unwrap());
-        first_col.push(split.next().unwrap());
-        second_col.push(split.next().unwrap());
-    }
+    let (mut first_col, mut second_col) = parse_columns(input);
 
     first_col.sort();
     second_col.sort();
@@ -33,17 +35,9 @@ fn first_part(input: &str) -> i32 {
 }
 
 fn second_part(input: &str) -> i32 {
-    let mut first_col: Vec<i32> = Vec::new();
+    let (first_col, second_col) = parse_columns(input);
     let mut hash_map: HashMap<i32, i32> = HashMap::new();
-
-    for line in input.lines() {
-        let mut split = line
-            .split_whitespace()
-            .map(|num| num.parse::<i32>().unwrap());
-        let left = split.next().unwrap();
-        let right = split.next().unwrap();
-
-        first_col.push(left);
+    for right in second_col {
         *hash_map.entry(right).or_insert(0) += 1;
     }
 
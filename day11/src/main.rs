@@ -1,5 +1,5 @@
 use ahash::AHashMap;
-use utils::test_solutions;
+use utils::{test_solutions, Cursor};
 
 fn main() {
     test_solutions(
@@ -15,10 +15,11 @@ fn main() {
 
 /// Parses the input into a hashmap of numbers and their number of occurrences.
 fn parse_input(input: &str) -> AHashMap<u64, u64> {
-    let numbers = input
-        .split_whitespace()
-        .map(|x| x.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
+    let mut cursor = Cursor::from(input);
+    let mut numbers = vec![];
+    while cursor.skip_until_digit() {
+        numbers.push(cursor.next_unsigned().unwrap());
+    }
 
     // Run through the numbers and add them to the hashmap
     let mut bundled_numbers = AHashMap::new();
@@ -1,4 +1,4 @@
-use utils::test_solutions;
+use utils::{parse, test_solutions, Point};
 
 fn main() {
     test_solutions(13, &first_part, Some(480), &second_part, Some(875318608908));
@@ -6,88 +6,50 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
-fn parse_to_matrix(input: &str) -> Vec<[f32; 6]> {
-    let mut matrices: Vec<[f32; 6]> = vec![];
-    for data in input.split("\n\n") {
-        let processed: Vec<f32> = data
-            .lines()
-            .map(|line| {
-                line.split(": ")
-                    .nth(1)
-                    .unwrap()
-                    .split(", ")
-                    .map(|s| s.split_at(2).1)
-                    .map(|s| s.parse::<f32>().unwrap())
-                    .collect::<Vec<f32>>()
-            })
-            .flatten()
-            .collect::<Vec<f32>>();
-        matrices.push(processed.try_into().unwrap());
+const PRIZE_OFFSET: i64 = 10000000000000;
+
+/// Parses every claw machine into its `[button_a, button_b, prize]` vectors.
+fn parse_machines(input: &str) -> Vec<[Point<i64>; 3]> {
+    parse!("Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}")
+        .parse_blocks(input)
+        .unwrap_or_else(|error| panic!("{error}"))
+        .into_iter()
+        .map(|[ax, ay, bx, by, px, py]: [i64; 6]| {
+            [Point::new(ax, ay), Point::new(bx, by), Point::new(px, py)]
+        })
+        .collect()
+}
+
+/// Solves `a * button_a + b * button_b = prize` with Cramer's rule, returns the token cost if it has integer solution.
+fn solve_machine([button_a, button_b, prize]: [Point<i64>; 3]) -> Option<i64> {
+    let det = button_a.x * button_b.y - button_a.y * button_b.x;
+    if det == 0 {
+        return None;
     }
 
-    matrices
+    let a_press = prize.x * button_b.y - prize.y * button_b.x;
+    let b_press = button_a.x * prize.y - button_a.y * prize.x;
+    if a_press % det != 0 || b_press % det != 0 {
+        return None;
+    }
+
+    Some(a_press / det * 3 + b_press / det)
 }
 
 /* ------------------- Solutions ------------------- */
 
-#[allow(unused_variables)]
 fn first_part(input: &str) -> i64 {
-    let matrices = parse_to_matrix(input);
-
-    let mut result: i64 = 0;
-    for matrix in matrices.iter() {
-        let x = matrix[0];
-        let y = matrix[2];
-        let z = matrix[1];
-        let w = matrix[3];
-        let a = matrix[4];
-        let b = matrix[5];
-
-        let det = w * x - y * z;
-        if det == 0.0 {
-            continue;
-        }
-
-        let b_press = (b * x - a * z) / det;
-        let a_press = (a - b_press * y) / x;
-
-        if b_press.fract() > 0.01 || a_press.fract() > 0.01 {
-            continue;
-        }
-
-        result += a_press as i64 * 3 + b_press as i64;
-    }
-
-    result
+    parse_machines(input)
+        .into_iter()
+        .filter_map(solve_machine)
+        .sum()
 }
 
-#[allow(unused_variables)]
 fn second_part(input: &str) -> i64 {
-    let matrices = parse_to_matrix(input);
-
-    let mut result: i64 = 0;
-    for matrix in matrices.iter() {
-        let x: f64 = matrix[0] as f64;
-        let y: f64 = matrix[2] as f64;
-        let z: f64 = matrix[1] as f64;
-        let w: f64 = matrix[3] as f64;
-        let a: f64 = 10000000000000.0 + matrix[4] as f64;
-        let b: f64 = 10000000000000.0 + matrix[5] as f64;
-
-        let det = w * x - y * z;
-        if det == 0.0 {
-            continue;
-        }
-
-        let b_press = (b * x - a * z) / det;
-        let a_press = (a - b_press * y) / x;
-
-        if b_press.fract() > 0.01 || a_press.fract() > 0.01 {
-            continue;
-        }
-
-        result += a_press as i64 * 3 + b_press as i64;
-    }
-
-    result
+    parse_machines(input)
+        .into_iter()
+        .filter_map(|[button_a, button_b, prize]| {
+            solve_machine([button_a, button_b, prize + PRIZE_OFFSET])
+        })
+        .sum()
 }
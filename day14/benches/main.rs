@@ -1,5 +1,5 @@
 use utils::{
-    switch_to_performance_core, test_both_parts_bytes, test_example_bytes,
+    get_input, switch_to_performance_core, test_both_parts_bytes, test_example_bytes, Rgb,
 };
 
 fn main() {
@@ -13,4 +13,15 @@ fn main() {
         &day14::part1,
         &day14::part2::<false>,
     );
+
+    // Optionally save the christmas tree, `cargo run -p day14 -- tree.png`
+    if let Some(path) = std::env::args().nth(1) {
+        let mut input = get_input(Some(14));
+        let frame = day14::tree_frame(unsafe { input.as_bytes_mut() });
+        let colour = |&c: &u8| if c == b'#' { Rgb::GREEN } else { Rgb::BLACK };
+        frame
+            .save_image(&path, colour)
+            .expect("Could not save the tree image");
+        println!("Saved the tree to {path}");
+    }
 }
@@ -4,7 +4,8 @@
 //! -- Part One --
 //! This part is straightforward, we just need to move the robots by some number of steps.
 //! We can do it pretty easily with `next_pos = pos + vel * steps` and `pos = next_pos % resolution`.
-//! The Tricky part to make it go fast is the parser. We can just use normal string parser, it is too slow.
+//! The Tricky part to make it go fast is the parser. A normal string parser is too slow, so we read the numbers
+//! straight out of the bytes with a `Cursor`.
 //! Also, instead of calculating which quarter they fall into, we just use a LUT to get it from [pos][vel]
 //!
 //! -- Part Two --
@@ -14,12 +15,14 @@
 //! Thanks to error margin, we can get away with only calculating the first 80 robots instead of all 500.
 //! We are also using 16 sized SIMD vectors to speed up the calculations.
 //! In the end, we are doing Chinese Remainder Theorem to get the final position.
+//! `tree_frame` replays the robots to that step, so the tree can be saved as an image.
 
 #![feature(portable_simd)]
 
 use std::simd::cmp::SimdOrd;
 use std::simd::prelude::SimdUint;
 use std::simd::Simd;
+use utils::{Cursor, Grid, Particles, Point};
 
 /* ------------------- Helpers ------------------- */
 
@@ -70,79 +73,28 @@ const LUT_HEIGHT: [[u8; 103]; 103] = {
     result
 };
 
+/// Parses the next `p=x,y v=vx,vy` robot, the velocities are wrapped into `0..101` and `0..103` so they stay unsigned.
 #[inline]
-unsafe fn parse_robot(pointer: &mut *const u8) -> (u8, u8, u8, u8) {
-    *pointer = pointer.add(3);
-
-    let mut x = **pointer - b'0';
-    *pointer = pointer.add(1);
-    if **pointer != b',' {
-        x = x * 10 + **pointer - b'0';
-        *pointer = pointer.add(1);
-    }
-    if **pointer != b',' {
-        x = x * 10 + **pointer - b'0';
-        *pointer = pointer.add(1);
-    }
-
-    *pointer = pointer.add(1);
-    let mut y = **pointer - b'0';
-    *pointer = pointer.add(1);
-    if **pointer != b' ' {
-        y = y * 10 + **pointer - b'0';
-        *pointer = pointer.add(1);
-    }
-    if **pointer != b' ' {
-        y = y * 10 + **pointer - b'0';
-        *pointer = pointer.add(1);
-    }
-
-    *pointer = pointer.add(3);
-
-    let mut neg_x = false;
-    let mut vx = **pointer - b'0';
-    *pointer = pointer.add(1);
-    if vx == u8::MAX - 2 {
-        neg_x = true;
-        vx = **pointer - b'0';
-        *pointer = pointer.add(1);
-    }
-    if **pointer != b',' {
-        vx = vx * 10 + **pointer - b'0';
-        *pointer = pointer.add(1);
-    }
-    if neg_x {
-        vx = 101 - vx;
-    }
-
-    *pointer = pointer.add(1);
-    let mut neg_y = false;
-    let mut vy = **pointer - b'0';
-    *pointer = pointer.add(1);
-    if vy == u8::MAX - 2 {
-        neg_y = true;
-        vy = **pointer - b'0';
-        *pointer = pointer.add(1);
-    }
-    if **pointer != b'\n' {
-        vy = vy * 10 + **pointer - b'0';
-        *pointer = pointer.add(1);
-    }
-    if neg_y {
-        vy = 103 - vy;
-    }
+fn parse_robot(cursor: &mut Cursor) -> (u8, u8, u8, u8) {
+    let x = cursor.next_unsigned::<u8>().unwrap();
+    let y = cursor.next_unsigned::<u8>().unwrap();
+    let vx = cursor.next_signed::<i16>().unwrap();
+    let vy = cursor.next_signed::<i16>().unwrap();
+
+    let vx = if vx < 0 { vx + 101 } else { vx };
+    let vy = if vy < 0 { vy + 103 } else { vy };
+    (x, y, vx as u8, vy as u8)
+}
 
-    (x, y, vx, vy)
+/// The first `SAMPLE_SIZE` robots, split into SIMD lanes of their coordinates and velocities.
+struct Robots {
+    x_positions: Vec<Simd<u16, SIMD_SIZE>>,
+    y_positions: Vec<Simd<u16, SIMD_SIZE>>,
+    x_velocities: Vec<Simd<u16, SIMD_SIZE>>,
+    y_velocities: Vec<Simd<u16, SIMD_SIZE>>,
 }
 
-fn parse_input(
-    bytes: &[u8],
-) -> (
-    Vec<Simd<u16, SIMD_SIZE>>,
-    Vec<Simd<u16, SIMD_SIZE>>,
-    Vec<Simd<u16, SIMD_SIZE>>,
-    Vec<Simd<u16, SIMD_SIZE>>,
-) {
+fn parse_input(bytes: &[u8]) -> Robots {
     let mut x_positions = Vec::with_capacity(SAMPLE_SIZE / SIMD_SIZE);
     let mut y_positions = Vec::with_capacity(SAMPLE_SIZE / SIMD_SIZE);
     let mut x_vels = Vec::with_capacity(SAMPLE_SIZE / SIMD_SIZE);
@@ -153,10 +105,10 @@ fn parse_input(
     let mut x_vel = Vec::with_capacity(SIMD_SIZE);
     let mut y_vel = Vec::with_capacity(SIMD_SIZE);
 
-    let mut pointer = bytes.as_ptr().wrapping_sub(1);
+    let mut cursor = Cursor::new(bytes);
     let mut robot_count: usize = 0;
     while robot_count < SAMPLE_SIZE {
-        let (x, y, vx, vy) = unsafe { parse_robot(&mut pointer) };
+        let (x, y, vx, vy) = parse_robot(&mut cursor);
         robot_count += 1;
 
         x_pos.push(x as u16);
@@ -164,7 +116,7 @@ fn parse_input(
         x_vel.push(vx as u16);
         y_vel.push(vy as u16);
 
-        if robot_count % SIMD_SIZE == 0 {
+        if robot_count.is_multiple_of(SIMD_SIZE) {
             x_positions.push(Simd::<u16, SIMD_SIZE>::from_slice(&x_pos));
             y_positions.push(Simd::<u16, SIMD_SIZE>::from_slice(&y_pos));
             x_vels.push(Simd::<u16, SIMD_SIZE>::from_slice(&x_vel));
@@ -177,7 +129,12 @@ fn parse_input(
         }
     }
 
-    (x_positions, y_positions, x_vels, y_vels)
+    Robots {
+        x_positions,
+        y_positions,
+        x_velocities: x_vels,
+        y_velocities: y_vels,
+    }
 }
 
 /* ------------------- Solutions ------------------- */
@@ -185,10 +142,9 @@ fn parse_input(
 pub fn part1(input: &mut [u8]) -> u32 {
     let mut quarter_vals: [u32; 4] = [0, 0, 0, 0];
 
-    let mut pointer = input.as_ptr().wrapping_sub(1);
-    let end_pointer = pointer.wrapping_add(input.len());
-    while pointer < end_pointer {
-        let (x, y, vx, vy) = unsafe { parse_robot(&mut pointer) };
+    let mut cursor = Cursor::new(input);
+    while cursor.skip_until_digit() {
+        let (x, y, vx, vy) = parse_robot(&mut cursor);
 
         // Calculate the next positions using a simple movement formula.
         let dx = LUT_WIDTH[x as usize][vx as usize];
@@ -212,7 +168,7 @@ pub fn part2<const TEST: bool>(input: &mut [u8]) -> u32 {
     let zero16 = Simd::<u16, SIMD_SIZE>::splat(0);
     let zero32 = Simd::<u32, SIMD_SIZE>::splat(0);
 
-    let mut robots = parse_input(&mut &*input);
+    let mut robots = parse_input(input);
 
     let threshold = 500 * SAMPLE_SIZE as u32;
     let mut lowest_x_score = threshold;
@@ -225,10 +181,10 @@ pub fn part2<const TEST: bool>(input: &mut [u8]) -> u32 {
     let mut wide_y_square_sum = Simd::<u32, SIMD_SIZE>::splat(0);
     for step_count in 1..103 {
         for idx in 0..SAMPLE_SIZE / SIMD_SIZE {
-            let x_pos = unsafe { robots.0.get_unchecked_mut(idx) };
-            let y_pos = unsafe { robots.1.get_unchecked_mut(idx) };
-            let x_vel = unsafe { robots.2.get_unchecked(idx) };
-            let y_vel = unsafe { robots.3.get_unchecked(idx) };
+            let x_pos = unsafe { robots.x_positions.get_unchecked_mut(idx) };
+            let y_pos = unsafe { robots.y_positions.get_unchecked_mut(idx) };
+            let x_vel = unsafe { robots.x_velocities.get_unchecked(idx) };
+            let y_vel = unsafe { robots.y_velocities.get_unchecked(idx) };
 
             *x_pos += *x_vel;
             *y_pos += *y_vel;
@@ -270,3 +226,25 @@ pub fn part2<const TEST: bool>(input: &mut [u8]) -> u32 {
 
     (51 * (x_steps * 103 + y_steps * 101)) % (101 * 103)
 }
+
+/// Moves every robot to the step found by `part2` and draws them on a grid, robots are `#`.
+pub fn tree_frame(input: &mut [u8]) -> Grid<u8> {
+    let steps = part2::<false>(input);
+
+    let mut robots = Particles::new(Point::new(101, 103));
+    let mut cursor = Cursor::new(input);
+    while cursor.skip_until_digit() {
+        let (x, y, vx, vy) = parse_robot(&mut cursor);
+        robots.push(
+            Point::new(x as i32, y as i32),
+            Point::new(vx as i32, vy as i32),
+        );
+    }
+
+    let mut grid = Grid::new(101, 103, b'.');
+    for position in robots.positions_after(steps as u64) {
+        grid[position] = b'#';
+    }
+
+    grid
+}
@@ -1,4 +1,4 @@
-use utils::test_solutions;
+use utils::{sections, test_solutions, Grid, Point, BOTTOM, LEFT, RIGHT, TOP};
 
 fn main() {
     test_solutions(15, &first_part, Some(10092), &second_part, Some(9021));
@@ -6,201 +6,150 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
-#[derive(Debug)]
-enum DIRECTION {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
-}
-
-impl DIRECTION {
-    fn to_vec(&self) -> (i32, i32) {
-        match self {
-            DIRECTION::UP => (0, -1),
-            DIRECTION::DOWN => (0, 1),
-            DIRECTION::LEFT => (-1, 0),
-            DIRECTION::RIGHT => (1, 0),
-        }
-    }
-}
-
 /// The input consists of a grid where @ is a robot, # is a wall and O is a box.
-fn parse_input(input: &str) -> (Vec<Vec<char>>, Vec<DIRECTION>) {
-    let data = input.split("\n\n").collect::<Vec<&str>>();
-    let grid = data[0]
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
-
-    let dirs = data[1]
-        .chars()
-        .map(|c| match c {
-            '^' => Some(DIRECTION::UP),
-            'v' => Some(DIRECTION::DOWN),
-            '<' => Some(DIRECTION::LEFT),
-            '>' => Some(DIRECTION::RIGHT),
+fn parse_input(input: &str) -> (Grid<u8>, Vec<Point>) {
+    let mut sections = sections(input);
+    let (grid, moves) = (sections.next().unwrap(), sections.next().unwrap());
+
+    let dirs = moves
+        .bytes()
+        .filter_map(|c| match c {
+            b'^' => Some(TOP),
+            b'v' => Some(BOTTOM),
+            b'<' => Some(LEFT),
+            b'>' => Some(RIGHT),
             _ => None,
         })
-        .filter_map(|x| x)
-        .collect::<Vec<DIRECTION>>();
+        .collect::<Vec<Point>>();
 
-    (grid, dirs)
+    (Grid::parse(grid), dirs)
 }
 
-/// Finds and replaces the robot in the grid with a space
-fn find_robot(grid: &mut Vec<Vec<char>>) -> (i32, i32) {
-    for y in 0..grid.len() {
-        for x in 0..grid[y].len() {
-            if grid[y][x] == '@' {
-                grid[y][x] = '.';
-                return (x as i32, y as i32);
-            }
-        }
-    }
-
-    (0, 0)
+/// Finds and replaces the robot in the grid with an empty space
+fn find_robot(grid: &mut Grid<u8>) -> Point {
+    let robot = grid.find(b'@').unwrap();
+    grid[robot] = b'.';
+    robot
 }
 
-/// Finds and returns all objects
-fn find_objects(grid: &Vec<Vec<char>>) -> Vec<(i32, i32)> {
-    let mut objects: Vec<(i32, i32)> = vec![];
-    for y in 0..grid.len() {
-        for x in 0..grid[y].len() {
-            if grid[y][x] == 'O' || grid[y][x] == '[' {
-                objects.push((x as i32, y as i32));
-            }
-        }
-    }
-
-    objects
+/// Sums the GPS coordinates of all boxes
+fn gps_sum(grid: &Grid<u8>) -> i32 {
+    grid.positions()
+        .filter(|&p| grid[p] == b'O' || grid[p] == b'[')
+        .map(|p| p.x + p.y * 100)
+        .sum()
 }
 
-fn can_push_box(grid: &Vec<Vec<char>>, (box_x, box_y): (i32, i32), direction: &DIRECTION) -> bool {
-    let mut dir_vec = direction.to_vec();
-    if dir_vec.0 == 1 {
-        dir_vec.0 = 2;
-    }
-
-    let next_box = (box_x + dir_vec.0, box_y + dir_vec.1);
-    match direction {
-        DIRECTION::UP | DIRECTION::DOWN => {
-            let left_char = grid[next_box.1 as usize][next_box.0 as usize];
-            let right_char = grid[next_box.1 as usize][next_box.0 as usize + 1];
+fn can_push_box(grid: &Grid<u8>, box_pos: Point, direction: Point) -> bool {
+    let step = if direction == RIGHT {
+        RIGHT * 2
+    } else {
+        direction
+    };
 
-            match (left_char, right_char) {
-                ('.', '[') => can_push_box(grid, (next_box.0 + 1, next_box.1), direction),
-                (']', '.') => can_push_box(grid, (next_box.0 - 1, next_box.1), direction),
-                ('[', ']') => can_push_box(grid, (next_box.0, next_box.1), direction),
-                (']', '[') => {
-                    can_push_box(grid, (next_box.0 - 1, next_box.1), direction)
-                        && can_push_box(grid, (next_box.0 + 1, next_box.1), direction)
-                }
-                ('#', _) => false,
-                (_, '#') => false,
-                _ => true,
+    let next_box = box_pos + step;
+    if direction.y != 0 {
+        match (grid[next_box], grid[next_box + RIGHT]) {
+            (b'.', b'[') => can_push_box(grid, next_box + RIGHT, direction),
+            (b']', b'.') => can_push_box(grid, next_box + LEFT, direction),
+            (b'[', b']') => can_push_box(grid, next_box, direction),
+            (b']', b'[') => {
+                can_push_box(grid, next_box + LEFT, direction)
+                    && can_push_box(grid, next_box + RIGHT, direction)
             }
+            (b'#', _) => false,
+            (_, b'#') => false,
+            _ => true,
         }
-        DIRECTION::LEFT | DIRECTION::RIGHT => {
-            match grid[next_box.1 as usize][next_box.0 as usize] {
-                '.' => true,
-                '#' => false,
-                _ => can_push_box(grid, next_box, direction),
-            }
+    } else {
+        match grid[next_box] {
+            b'.' => true,
+            b'#' => false,
+            _ => can_push_box(grid, next_box, direction),
         }
     }
 }
 
-fn push_box(grid: &mut Vec<Vec<char>>, (box_x, box_y): (i32, i32), direction: &DIRECTION) -> bool {
-    let mut dir_vec = direction.to_vec();
-    if dir_vec.0 == 1 {
-        dir_vec.0 = 2;
-    }
+fn push_box(grid: &mut Grid<u8>, box_pos: Point, direction: Point) -> bool {
+    let step = if direction == RIGHT {
+        RIGHT * 2
+    } else {
+        direction
+    };
 
-    let pushed = can_push_box(grid, (box_x, box_y), direction);
+    let pushed = can_push_box(grid, box_pos, direction);
     if !pushed {
         return false;
     }
 
-    let mut next_box = (box_x + dir_vec.0, box_y + dir_vec.1);
-    match direction {
-        DIRECTION::UP | DIRECTION::DOWN => {
-            let left_char = grid[next_box.1 as usize][next_box.0 as usize];
-            let right_char = grid[next_box.1 as usize][next_box.0 as usize + 1];
-
-            match (left_char, right_char) {
-                ('.', '[') => push_box(grid, (next_box.0 + 1, next_box.1), direction),
-                (']', '.') => push_box(grid, (next_box.0 - 1, next_box.1), direction),
-                ('[', ']') => push_box(grid, (next_box.0, next_box.1), direction),
-                (']', '[') => {
-                    let _ = push_box(grid, (next_box.0 - 1, next_box.1), direction);
-                    push_box(grid, (next_box.0 + 1, next_box.1), direction)
-                }
-                _ => true,
+    let next_box = box_pos + step;
+    if direction.y != 0 {
+        match (grid[next_box], grid[next_box + RIGHT]) {
+            (b'.', b'[') => push_box(grid, next_box + RIGHT, direction),
+            (b']', b'.') => push_box(grid, next_box + LEFT, direction),
+            (b'[', b']') => push_box(grid, next_box, direction),
+            (b']', b'[') => {
+                let _ = push_box(grid, next_box + LEFT, direction);
+                push_box(grid, next_box + RIGHT, direction)
             }
+            _ => true,
         }
-        DIRECTION::LEFT | DIRECTION::RIGHT => {
-            match grid[next_box.1 as usize][next_box.0 as usize] {
-                ']' => push_box(grid, (next_box.0 - 1, next_box.1), direction),
-                '[' => push_box(grid, (next_box.0, next_box.1), direction),
-                _ => true,
-            }
+    } else {
+        match grid[next_box] {
+            b']' => push_box(grid, next_box + LEFT, direction),
+            b'[' => push_box(grid, next_box, direction),
+            _ => true,
         }
     };
 
-    if dir_vec.0 == 2 {
-        next_box.0 -= 1;
-    }
+    let next_box = box_pos + direction;
 
-    grid[box_y as usize][box_x as usize] = '.';
-    grid[box_y as usize][box_x as usize + 1] = '.';
+    grid[box_pos] = b'.';
+    grid[box_pos + RIGHT] = b'.';
 
-    grid[next_box.1 as usize][next_box.0 as usize] = '[';
-    grid[next_box.1 as usize][next_box.0 as usize + 1] = ']';
+    grid[next_box] = b'[';
+    grid[next_box + RIGHT] = b']';
 
     true
 }
 
 /* ------------------- Solutions ------------------- */
 
-#[allow(unused_variables)]
 fn first_part(input: &str) -> i32 {
     let (mut grid, directions) = parse_input(input);
     let mut robot = find_robot(&mut grid);
 
     for dir in directions {
         // Try to move towards the direction
+        let next_dir = robot + dir;
 
-        let dir_vec = dir.to_vec();
-        let next_dir = (robot.0 + dir_vec.0, robot.1 + dir_vec.1);
-
-        match grid[next_dir.1 as usize][next_dir.0 as usize] {
-            '.' => {
+        match grid[next_dir] {
+            b'.' => {
                 // Empty space, can move
                 robot = next_dir;
             }
-            'O' => {
+            b'O' => {
                 // Hit a box, move the box as much as possible
 
-                let mut found_empty = (-1, -1);
+                let mut found_empty = None;
                 let mut next_grid = next_dir;
                 loop {
-                    next_grid = (next_grid.0 + dir_vec.0, next_grid.1 + dir_vec.1);
-                    let next_char = grid[next_grid.1 as usize][next_grid.0 as usize];
+                    next_grid += dir;
+                    let next_char = grid[next_grid];
 
-                    if next_char == '.' {
-                        found_empty = next_grid;
+                    if next_char == b'.' {
+                        found_empty = Some(next_grid);
                         break;
-                    } else if next_char == '#' {
+                    } else if next_char == b'#' {
                         // Hit a wall, can't move the boxes
                         break;
                     }
                 }
 
-                if found_empty.0 > 0 {
+                if let Some(empty) = found_empty {
                     // Move the main box there and guard to current position
-                    grid[found_empty.1 as usize][found_empty.0 as usize] = 'O';
-                    grid[next_dir.1 as usize][next_dir.0 as usize] = '.';
+                    grid[empty] = b'O';
+                    grid[next_dir] = b'.';
                     robot = next_dir;
                 }
             }
@@ -208,76 +157,42 @@ fn first_part(input: &str) -> i32 {
         }
     }
 
-    let objects = find_objects(&grid);
-    let mut result = 0;
-    for object in objects {
-        result += object.0 + object.1 * 100;
-    }
-
-    result
+    gps_sum(&grid)
 }
 
-#[allow(unused_variables)]
 fn second_part(input: &str) -> i32 {
-    let (mut grid, directions) = parse_input(input);
-    grid = grid
-        .iter()
-        .map(|row| {
-            row.iter()
-                .flat_map(|c| {
-                    if *c == 'O' {
-                        vec!['[', ']']
-                    } else if *c == '@' {
-                        vec!['@', '.']
-                    } else {
-                        vec![*c, *c]
-                    }
-                })
-                .collect::<Vec<char>>()
-        })
-        .collect::<Vec<Vec<char>>>();
+    let (grid, directions) = parse_input(input);
+    let mut grid = grid.map_expand(|c| match c {
+        b'O' => *b"[]",
+        b'@' => *b"@.",
+        c => [c, c],
+    });
 
     let mut robot = find_robot(&mut grid);
 
     for dir in directions {
         // Try to move towards the direction
-        grid[robot.1 as usize][robot.0 as usize] = '.';
-
-        let dir_vec = dir.to_vec();
-        let next_dir = (robot.0 + dir_vec.0, robot.1 + dir_vec.1);
+        let next_dir = robot + dir;
+        let next_char = grid[next_dir];
 
-        match grid[next_dir.1 as usize][next_dir.0 as usize] {
-            '.' => {
+        match next_char {
+            b'.' => {
                 // Empty space, can move
                 robot = next_dir;
             }
-            '[' => {
-                // Hit left side of a box, move the box as much as possible
-                if push_box(&mut grid, next_dir, &dir) {
-                    robot = next_dir;
-                }
+            // Hit left side of a box, move the box as much as possible
+            b'[' if push_box(&mut grid, next_dir, dir) => {
+                robot = next_dir;
             }
-            ']' => {
-                // Hit right side of a box, move the box as much as possible
-                if push_box(&mut grid, (next_dir.0 - 1, next_dir.1), &dir) {
-                    robot = next_dir;
-                }
+            // Hit right side of a box, move the box as much as possible
+            b']' if push_box(&mut grid, next_dir + LEFT, dir) => {
+                robot = next_dir;
             }
             _ => {
-                // Hit a wall or unknown character, can't move
+                // Hit a wall, a stuck box or unknown character, can't move
             }
         }
-
-        grid[robot.1 as usize][robot.0 as usize] = '@';
     }
 
-    let objects = find_objects(&grid);
-    let mut result = 0;
-    for object in objects {
-        result += object.0 + object.1 * 100;
-    }
-
-    // 1574964 <<
-
-    result
+    gps_sum(&grid)
 }
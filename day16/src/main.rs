@@ -1,5 +1,4 @@
-use rayon::prelude::*;
-use utils::{test_solutions, Grid, Point, DIRECTIONS};
+use utils::{test_solutions, BitGrid, BucketQueue, Grid, Point, DIRECTIONS};
 
 fn main() {
     test_solutions(16, &first_part, Some(11048), &second_part, Some(64));
@@ -13,135 +12,72 @@ fn parse_input(input: &str) -> (Grid<u8>, Point) {
     (grid, start)
 }
 
-/* ------------------- Solutions ------------------- */
-
-fn first_part(input: &str) -> u32 {
-    let (grid, start) = parse_input(input);
-
-    let mut weighted_map = grid.clone_with(u32::MAX);
-    let mut priority_leaves: Vec<(Point, usize, u32)> = vec![(start, 0, 0)];
-    let mut secondary_leaves: Vec<(Point, usize, u32)> = vec![];
-    weighted_map[start] = 0;
-
-    let mut end_weight = u32::MAX;
-    while !priority_leaves.is_empty() {
-        let (leaf_pos, leaf_dir, leaf_weight) = priority_leaves.pop().unwrap();
-
-        if leaf_weight >= end_weight {
+/// Runs Dijkstra over every (position, direction) state, the cheapest cost of each one is stored in the returned grid.
+/// Moving forward costs 1 and turning in place costs 1000, so a bucket queue with 1001 buckets is enough.
+/// Stops as soon as the end is reached, every state cheaper than the end is final by then.
+fn lowest_costs(grid: &Grid<u8>, start: Point) -> (Grid<[u32; 4]>, u32) {
+    let mut costs = grid.clone_with([u32::MAX; 4]);
+    let mut queue = BucketQueue::new(1000);
+    costs[start][0] = 0;
+    queue.push(0, (start, 0));
+
+    while let Some((cost, (position, direction))) = queue.pop() {
+        if cost > costs[position][direction] {
             continue;
         }
 
-        if grid[leaf_pos] == b'E' {
-            end_weight = leaf_weight;
-            continue;
+        if grid[position] == b'E' {
+            return (costs, cost);
         }
 
         // Directional vectors
-        let left = (leaf_dir + 3) % 4;
-        let right = (leaf_dir + 1) % 4;
-        let neighbors_check = [
-            (leaf_pos + DIRECTIONS[leaf_dir], leaf_dir, leaf_weight + 1),
-            (leaf_pos + DIRECTIONS[left], left, leaf_weight + 1001),
-            (leaf_pos + DIRECTIONS[right], right, leaf_weight + 1001),
+        let left = (direction + 3) % 4;
+        let right = (direction + 1) % 4;
+        let moves = [
+            (position + DIRECTIONS[direction], direction, cost + 1),
+            (position, left, cost + 1000),
+            (position, right, cost + 1000),
         ];
 
-        for neighbor in neighbors_check {
-            let (next_position, next_direction, next_weight) = neighbor;
+        for (next_position, next_direction, next_cost) in moves {
             if grid[next_position] == b'#' {
                 continue;
             }
 
-            let prev_weight = &mut weighted_map[next_position];
-            if *prev_weight < next_weight {
+            let best_cost = &mut costs[next_position][next_direction];
+            if *best_cost <= next_cost {
                 continue;
             }
-            *prev_weight = next_weight;
-
-            if next_direction == leaf_dir {
-                priority_leaves.push(neighbor);
-            } else {
-                secondary_leaves.push(neighbor);
-            }
-        }
-
-        if priority_leaves.is_empty() {
-            let temp = priority_leaves;
-            priority_leaves = secondary_leaves;
-            secondary_leaves = temp;
+            *best_cost = next_cost;
+            queue.push(next_cost, (next_position, next_direction));
         }
     }
 
-    end_weight
+    panic!("No path found");
+}
+
+/* ------------------- Solutions ------------------- */
+
+fn first_part(input: &str) -> u32 {
+    let (grid, start) = parse_input(input);
+    lowest_costs(&grid, start).1
 }
 
 fn second_part(input: &str) -> usize {
     let (grid, start) = parse_input(input);
     let end = grid.find(b'E').unwrap();
-
-    let mut weighted_map = grid.clone_with([u32::MAX; 4]);
-
-    let mut priority_leaves: Vec<(Point, usize, u32)> = vec![(start, 0, 0)];
-    let mut secondary_leaves: Vec<(Point, usize, u32)> = vec![];
-    weighted_map[start][0] = 0;
-
-    let mut end_weight = u32::MAX;
-    while !priority_leaves.is_empty() {
-        let (leaf_pos, leaf_dir, leaf_weight) = priority_leaves.pop().unwrap();
-
-        if leaf_weight >= end_weight {
-            continue;
-        }
-
-        if leaf_pos == end {
-            end_weight = leaf_weight;
-            continue;
-        }
-
-        // Directional vectors
-        let left = (leaf_dir + 3) % 4;
-        let right = (leaf_dir + 1) % 4;
-        let neighbors_check = [
-            (leaf_pos + DIRECTIONS[leaf_dir], leaf_dir, leaf_weight + 1),
-            (leaf_pos, left, leaf_weight + 1000),
-            (leaf_pos, right, leaf_weight + 1000),
-        ];
-
-        for neighbor in neighbors_check {
-            let (next_position, next_direction, next_weight) = neighbor;
-            if grid[next_position] == b'#' {
-                continue;
-            }
-
-            let prev_weight = &mut weighted_map[next_position][next_direction];
-            if *prev_weight < next_weight {
-                continue;
-            }
-            *prev_weight = next_weight;
-
-            if next_direction == leaf_dir {
-                priority_leaves.push(neighbor);
-            } else {
-                secondary_leaves.push(neighbor);
-            }
-        }
-
-        if priority_leaves.is_empty() {
-            let temp = priority_leaves;
-            priority_leaves = secondary_leaves;
-            secondary_leaves = temp;
-        }
-    }
+    let (mut weighted_map, end_weight) = lowest_costs(&grid, start);
 
     let mut tiles_to_check: Vec<(Point, usize, u32)> = vec![];
     for dir in 0..4 {
         tiles_to_check.push((end, dir, end_weight));
     }
 
-    let mut seen_tiles = grid.clone_with(false);
+    let mut seen_tiles = BitGrid::new(grid.width, grid.height);
     while !tiles_to_check.is_empty() {
         let (leaf_pos, leaf_dir, leaf_weight) = tiles_to_check.pop().unwrap();
 
-        seen_tiles[leaf_pos] = true;
+        seen_tiles.insert(leaf_pos);
         if leaf_pos == start {
             continue;
         }
@@ -170,5 +106,5 @@ fn second_part(input: &str) -> usize {
         }
     }
 
-    seen_tiles.data.par_iter().filter(|&x| *x).count()
+    seen_tiles.count_ones()
 }
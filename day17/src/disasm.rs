@@ -0,0 +1,235 @@
+//! Disassembler and symbolic simplifier for the 3-bit programs of day 17.
+//!
+//! `disassemble` lists every instruction with its mnemonic, combo operands are shown as the register they read.
+//! `simplify` runs one loop iteration on expressions instead of numbers, which gives the output and the next A
+//! in terms of the A at the start of the iteration, like `(A & 7) ^ ((A >> ((A & 7) ^ 5)) & 7) ^ 3`.
+//! It only understands the usual shape of the puzzle programs, a single loop with `jnz 0` as the last instruction.
+
+use crate::vm::{A, B, C};
+use std::fmt::{Display, Formatter};
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: u8,
+    pub operand: u8,
+}
+
+impl Instruction {
+    /// Returns whether the operand is a combo operand, `bxl` and `jnz` use it as a literal and `bxc` ignores it.
+    pub fn has_combo(&self) -> bool {
+        matches!(self.opcode, 0 | 2 | 5 | 6 | 7)
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some(mnemonic) = MNEMONICS.get(self.opcode as usize) else {
+            return write!(f, "??? {} {}", self.opcode, self.operand);
+        };
+
+        match (self.opcode, self.operand) {
+            (4, _) => write!(f, "{mnemonic}"),
+            (_, 4..=6) if self.has_combo() => write!(
+                f,
+                "{mnemonic} {}",
+                ['A', 'B', 'C'][self.operand as usize - 4]
+            ),
+            (_, 7) if self.has_combo() => write!(f, "{mnemonic} <reserved>"),
+            _ => write!(f, "{mnemonic} {}", self.operand),
+        }
+    }
+}
+
+/// Returns the listing of the program, one `ip: instruction` line per instruction.
+pub fn disassemble(program: &[u8]) -> String {
+    program
+        .chunks(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let instruction = Instruction {
+                opcode: pair[0],
+                operand: pair.get(1).copied().unwrap_or_default(),
+            };
+            format!("{:>2}: {instruction}", i * 2)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/* ------------------- Expressions ------------------- */
+
+/// A value of one loop iteration, the registers stand for their value at the start of it.
+/// Expressions are only built through `xor`, `shr` and `low`, which keep them simplified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Register(usize),
+    Const(u64),
+    Xor(Vec<Expr>),
+    Shr(Box<Expr>, Box<Expr>),
+    /// The lowest 3 bits, `x & 7`.
+    Low(Box<Expr>),
+}
+
+impl Expr {
+    /// Returns whether the value always fits into 3 bits.
+    fn is_low(&self) -> bool {
+        match self {
+            Expr::Const(c) => *c < 8,
+            Expr::Low(_) => true,
+            Expr::Xor(terms) => terms.iter().all(Expr::is_low),
+            _ => false,
+        }
+    }
+
+    /// Xors the terms together, nested xors are flattened, constants folded and equal pairs cancel out.
+    fn xor(left: Expr, right: Expr) -> Expr {
+        let mut constant = 0;
+        let mut terms: Vec<Expr> = vec![];
+        for term in [left, right] {
+            let flat = match term {
+                Expr::Xor(inner) => inner,
+                other => vec![other],
+            };
+            for term in flat {
+                match term {
+                    Expr::Const(c) => constant ^= c,
+                    term => match terms.iter().position(|t| *t == term) {
+                        Some(i) => _ = terms.remove(i),
+                        None => terms.push(term),
+                    },
+                }
+            }
+        }
+
+        if constant != 0 {
+            terms.push(Expr::Const(constant));
+        }
+        match terms.len() {
+            0 => Expr::Const(0),
+            1 => terms.pop().unwrap(),
+            _ => Expr::Xor(terms),
+        }
+    }
+
+    fn shr(value: Expr, amount: Expr) -> Expr {
+        match (value, amount) {
+            (value, Expr::Const(0)) => value,
+            (Expr::Const(v), Expr::Const(a)) => {
+                Expr::Const(v.checked_shr(a.min(64) as u32).unwrap_or(0))
+            }
+            (Expr::Const(0), _) => Expr::Const(0),
+            (Expr::Shr(inner, first), Expr::Const(a)) if matches!(*first, Expr::Const(_)) => {
+                let Expr::Const(first) = *first else {
+                    unreachable!()
+                };
+                Expr::shr(*inner, Expr::Const(first + a))
+            }
+            (value, amount) => Expr::Shr(Box::new(value), Box::new(amount)),
+        }
+    }
+
+    /// Keeps the lowest 3 bits, it distributes over xor so every term is masked on its own.
+    fn low(value: Expr) -> Expr {
+        match value {
+            value if value.is_low() => value,
+            Expr::Const(c) => Expr::Const(c & 7),
+            Expr::Xor(terms) => terms
+                .into_iter()
+                .map(Expr::low)
+                .fold(Expr::Const(0), Expr::xor),
+            value => Expr::Low(Box::new(value)),
+        }
+    }
+
+    /// Writes a nested expression, everything except registers and constants is parenthesized.
+    fn fmt_child(f: &mut Formatter<'_>, child: &Expr) -> std::fmt::Result {
+        match child {
+            Expr::Register(_) | Expr::Const(_) => write!(f, "{child}"),
+            _ => write!(f, "({child})"),
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Register(r) => write!(f, "{}", ['A', 'B', 'C'][*r]),
+            Expr::Const(c) => write!(f, "{c}"),
+            Expr::Xor(terms) => {
+                for (i, term) in terms.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ^ ")?;
+                    }
+                    Expr::fmt_child(f, term)?;
+                }
+                Ok(())
+            }
+            Expr::Shr(value, amount) => {
+                Expr::fmt_child(f, value)?;
+                write!(f, " >> ")?;
+                Expr::fmt_child(f, amount)
+            }
+            Expr::Low(value) => {
+                Expr::fmt_child(f, value)?;
+                write!(f, " & 7")
+            }
+        }
+    }
+}
+
+/* ------------------- Simplifier ------------------- */
+
+/// One loop iteration of the program, in terms of the registers at the start of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Iteration {
+    pub outputs: Vec<Expr>,
+    pub registers: [Expr; 3],
+}
+
+impl Display for Iteration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for output in &self.outputs {
+            writeln!(f, "out {output}")?;
+        }
+        write!(f, "A = {}", self.registers[A])
+    }
+}
+
+/// Symbolically executes the loop body of the program, returns `None` if it is not a single loop
+/// ending in `jnz 0` or if it uses the reserved combo operand.
+pub fn simplify(program: &[u8]) -> Option<Iteration> {
+    let body = match program {
+        [body @ .., 3, 0] => body,
+        _ => return None,
+    };
+    if body.len() % 2 != 0 {
+        return None;
+    }
+
+    let mut registers = [Expr::Register(A), Expr::Register(B), Expr::Register(C)];
+    let mut outputs = vec![];
+    for pair in body.chunks(2) {
+        let (opcode, operand) = (pair[0], pair[1]);
+        let combo = || match operand {
+            0..=3 => Some(Expr::Const(operand as u64)),
+            4..=6 => Some(registers[operand as usize - 4].clone()),
+            _ => None,
+        };
+
+        match opcode {
+            0 => registers[A] = Expr::shr(registers[A].clone(), combo()?),
+            6 => registers[B] = Expr::shr(registers[A].clone(), combo()?),
+            7 => registers[C] = Expr::shr(registers[A].clone(), combo()?),
+            1 => registers[B] = Expr::xor(registers[B].clone(), Expr::Const(operand as u64)),
+            2 => registers[B] = Expr::low(combo()?),
+            4 => registers[B] = Expr::xor(registers[B].clone(), registers[C].clone()),
+            5 => outputs.push(Expr::low(combo()?)),
+            // Jumps inside of the body are not a single loop anymore
+            _ => return None,
+        }
+    }
+
+    Some(Iteration { outputs, registers })
+}
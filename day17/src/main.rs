@@ -1,6 +1,17 @@
-use utils::test_solutions;
+mod disasm;
+mod vm;
+
+use utils::{get_input, parse, test_solutions, List};
+use vm::Vm;
 
 fn main() {
+    if std::env::var_os("DAY17_TRACE").is_some() {
+        print_trace(&get_input(Some(17)));
+    }
+    if std::env::var_os("DAY17_DISASM").is_some() {
+        print_disassembly(&get_input(Some(17)));
+    }
+
     test_solutions(
         17,
         &first_part,
@@ -12,159 +23,99 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
+/// Upper bound on executed instructions, the puzzle programs halt after a few hundred.
+const INSTRUCTION_LIMIT: usize = 1_000_000;
+
 /// Returns (registers, program)
-fn parse_input(input: &str) -> ([i32; 3], Vec<i32>) {
-    let mut registers = [0; 3];
-    let mut program = vec![];
-
-    let data = input.split("\n\n").collect::<Vec<&str>>();
-    for (i, line) in data[0].lines().enumerate() {
-        registers[i] = line.split(": ").collect::<Vec<&str>>()[1]
-            .parse::<i32>()
-            .unwrap();
-    }
+fn parse_input(input: &str) -> ([u64; 3], Vec<u8>) {
+    let (a, b, c, List(program)) =
+        parse!("Register A: {}\nRegister B: {}\nRegister C: {}\n\nProgram: {}")
+            .parse(input)
+            .unwrap_or_else(|error| panic!("{error}"));
+
+    ([a, b, c], program)
+}
 
-    for char in data[1].split(": ").collect::<Vec<&str>>()[1]
-        .split(',')
-        .collect::<Vec<&str>>()
-    {
-        program.push(char.parse::<i32>().unwrap());
+/// Runs the program and prints every executed instruction with the registers after it, then the error if any.
+fn print_trace(input: &str) {
+    let (registers, program) = parse_input(input);
+    let mut vm = Vm::new(&program, registers)
+        .with_limit(INSTRUCTION_LIMIT)
+        .with_trace();
+    let error = vm.run().err();
+    for step in vm.trace() {
+        println!("{step}");
     }
+    if let Some(error) = error {
+        println!("{error}");
+    }
+}
 
-    (registers, program)
+/// Prints the disassembled program and the simplified loop iteration.
+fn print_disassembly(input: &str) {
+    let (_, program) = parse_input(input);
+    println!("{}", disasm::disassemble(&program));
+    match disasm::simplify(&program) {
+        Some(iteration) => println!("{iteration}"),
+        None => println!("The program is not a single loop"),
+    }
 }
 
-fn execute_program(
-    registers: &mut [i32; 3],
-    program: Vec<i32>,
-    inst_pointer: &mut i32,
-    results: &mut Vec<i32>,
-) {
-    // If we are out of the program, we stop.
-    if *inst_pointer as usize >= program.len() {
-        return;
+/// Extends the 3-bit digits of register A found so far by one more digit, from the most significant one down.
+/// Every program loop outputs one value and shifts A right by 3, so the digits of A found so far produce
+/// the last outputs and one more digit has to reproduce `program[index..]`.
+/// Candidates are tried from 0 to 7, the first complete match is the smallest A.
+fn find_quine(program: &[u8], registers: [u64; 3], index: usize, a: u64) -> Option<u64> {
+    // Another digit would shift bits out of A, so no 64-bit A outputs the longer suffix
+    if a.leading_zeros() < 3 {
+        return None;
     }
 
-    // First we read the opcode and operand and increment the instruction pointer each step.
-    let opcode = program[*inst_pointer as usize];
-    let literal_operand = program[*inst_pointer as usize + 1];
-
-    // Then depending on the opcode, we calculate the combo operand.
-    let combo_operand = match literal_operand {
-        0 => 0,
-        1 => 1,
-        2 => 2,
-        3 => 3,
-        7 => -1,
-        _ => registers[literal_operand as usize - 4],
-    };
-
-    // Now we execute the opcode.
-    match opcode {
-        0 => {
-            // ADV (sets A to A / 2^num)
-            registers[0] >>= combo_operand;
-        }
-        1 => {
-            // BXL (sets B to XOR of B and num)
-            registers[1] ^= literal_operand;
-        }
-        2 => {
-            // BST (sets B to num % 8)
-            registers[1] = combo_operand & 0b111;
-        }
-        3 => {
-            // JNZ (if A is 0, nothing else jumps to num)
-            if registers[0] != 0 {
-                *inst_pointer = literal_operand - 2;
-            }
-        }
-        4 => {
-            // BXC (sets B to XOR of B and C)
-            registers[1] ^= registers[2];
-        }
-        5 => {
-            // OUT (prints num % 8)
-            results.push(combo_operand & 0b111);
+    for digit in 0..8 {
+        let candidate = a << 3 | digit;
+        let mut vm =
+            Vm::new(program, [candidate, registers[1], registers[2]]).with_limit(INSTRUCTION_LIMIT);
+        let output = vm.run().unwrap_or_else(|error| panic!("{error}"));
+        if output != &program[index..] {
+            continue;
         }
-        6 => {
-            // BDV (sets B to A / 2^num)
-            registers[1] = registers[0] >> combo_operand;
+
+        if index == 0 {
+            return Some(candidate);
         }
-        7 => {
-            // CDV (sets C to A / 2^num)
-            registers[2] = registers[0] >> combo_operand;
+        if let Some(result) = find_quine(program, registers, index - 1, candidate) {
+            return Some(result);
         }
-        _ => panic!("Unknown opcode"),
     }
 
-    // After execution, we call the next instruction.
-    *inst_pointer += 2;
-    execute_program(registers, program, inst_pointer, results);
-}
-
-/// Hard coded short version of the input program.
-fn short_program(a: i64, results: &mut Vec<i64>) {
-    let n = a & 0b111;
-
-    let b = n ^ 0b11 ^ (a >> (n ^ 0b101));
-    let a = a >> 3;
-
-    results.push(b % 8);
-
-    if a == 0 {
-        return;
-    }
-    short_program(a, results);
+    None
 }
 
 /* ------------------- Solutions ------------------- */
 
-/// Parses the registers/program and executes the program using the helper function.
-/// Helper function gets the opcode/operand and executes it.
-/// After execution, it calls itself again with increased instruction pointer.
-#[allow(unused_variables)]
+/// Parses the registers/program and runs it on the VM, joining the output with commas.
 fn first_part(input: &str) -> String {
-    let (mut registers, program) = parse_input(input);
-    let mut results: Vec<i32> = vec![];
-    execute_program(&mut registers, program, &mut 0, &mut results);
+    let (registers, program) = parse_input(input);
 
-    results
+    let mut vm = Vm::new(&program, registers).with_limit(INSTRUCTION_LIMIT);
+    let output = vm.run().unwrap_or_else(|error| panic!("{error}"));
+    output
         .iter()
         .map(|n| n.to_string())
         .collect::<Vec<String>>()
         .join(",")
 }
 
-/// Kind of cheaty but works. Basically I "manually" compiled the program down to check faster.
-/// 3 most important bits are used to calculate last operand.
-/// 6 most important bits are used to calculate last opcode, and it continues like this.
-/// Solution starts with 0b000 and checks for all 1000 numbers (worst case).
-/// If it results in the correct "target number" it will move the number 3 bits to the left and continues.
-#[allow(unused_variables)]
-fn second_part(input: &str) -> i64 {
+/// Searches for the register A that makes the program output itself, building it 3 bits at a time.
+/// Starting from the last value of the program, every step tries all 8 next digits of A on the VM
+/// and backtracks when none of them reproduces the longer suffix.
+fn second_part(input: &str) -> u64 {
     let (registers, program) = parse_input(input);
 
-    let target = [2, 4, 1, 5, 7, 5, 1, 6, 0, 3, 4, 3, 5, 5, 3, 0];
-    let mut check = target.len() - 1;
-
-    let mut result = 0;
-    loop {
-        let mut results: Vec<i64> = vec![];
-        short_program(result, &mut results);
-
-        if results[0] == target[check] {
-            if check == 0 {
-                break;
-            }
-            result <<= 3;
-            check -= 1;
-
-            continue;
-        }
-
-        result += 1
-    }
-
-    result
+    let last = program
+        .len()
+        .checked_sub(1)
+        .expect("The program is empty, so it has nothing to output");
+    find_quine(&program, registers, last, 0)
+        .expect("No 64-bit register A makes the program output itself")
 }
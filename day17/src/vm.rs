@@ -0,0 +1,185 @@
+//! The 3-bit computer of day 17.
+//!
+//! `Vm` runs a program of 3-bit values with three `u64` registers in a plain fetch/execute loop.
+//! Running can be limited to a number of instructions, so a program that never halts returns an error instead.
+//! Combo operand 7 is reserved and an opcode outside of `0..8` does not exist, both are errors as well.
+//! With `with_trace`, every executed instruction is recorded as a `Step` together with the state after it.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Indices of the registers in `Vm::registers`.
+pub const A: usize = 0;
+pub const B: usize = 1;
+pub const C: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VmError {
+    /// Combo operand 7 was used at the given instruction pointer.
+    ReservedOperand { ip: usize },
+    /// The value at the given instruction pointer is not an opcode.
+    UnknownOpcode { ip: usize, opcode: u8 },
+    /// The program did not halt within the given number of instructions.
+    InstructionLimit { limit: usize },
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::ReservedOperand { ip } => write!(f, "Reserved combo operand 7 at ip {ip}"),
+            VmError::UnknownOpcode { ip, opcode } => {
+                write!(f, "Unknown opcode {opcode} at ip {ip}")
+            }
+            VmError::InstructionLimit { limit } => {
+                write!(f, "Program did not halt after {limit} instructions")
+            }
+        }
+    }
+}
+
+impl Error for VmError {}
+
+/// A single executed instruction, the registers are the ones after it ran.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub ip: usize,
+    pub opcode: u8,
+    pub operand: u8,
+    pub registers: [u64; 3],
+    pub output: Option<u8>,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [a, b, c] = self.registers;
+        write!(
+            f,
+            "ip={:<3} {} {} | A={a} B={b} C={c}",
+            self.ip, self.opcode, self.operand
+        )?;
+        if let Some(output) = self.output {
+            write!(f, " | out {output}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Vm<'a> {
+    pub registers: [u64; 3],
+    pub ip: usize,
+    pub output: Vec<u8>,
+    program: &'a [u8],
+    executed: usize,
+    limit: Option<usize>,
+    trace: Option<Vec<Step>>,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(program: &'a [u8], registers: [u64; 3]) -> Self {
+        Vm {
+            registers,
+            ip: 0,
+            output: vec![],
+            program,
+            executed: 0,
+            limit: None,
+            trace: None,
+        }
+    }
+
+    /// Stops with `VmError::InstructionLimit` after executing `limit` instructions.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Records every executed instruction, see `trace`.
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Returns the recorded steps, empty if tracing is off.
+    pub fn trace(&self) -> &[Step] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Runs until the instruction pointer leaves the program and returns the output.
+    pub fn run(&mut self) -> Result<&[u8], VmError> {
+        while self.step()? {}
+        Ok(&self.output)
+    }
+
+    /// Executes a single instruction, returns `false` if the program has already halted.
+    pub fn step(&mut self) -> Result<bool, VmError> {
+        let (Some(&opcode), Some(&operand)) =
+            (self.program.get(self.ip), self.program.get(self.ip + 1))
+        else {
+            return Ok(false);
+        };
+
+        if self.limit.is_some_and(|limit| self.executed >= limit) {
+            return Err(VmError::InstructionLimit {
+                limit: self.executed,
+            });
+        }
+        self.executed += 1;
+
+        let ip = self.ip;
+        let mut output = None;
+        self.ip += 2;
+
+        match opcode {
+            // ADV, BDV and CDV divide A by 2^combo into A, B and C
+            0 => self.registers[A] = self.divide(ip, operand)?,
+            6 => self.registers[B] = self.divide(ip, operand)?,
+            7 => self.registers[C] = self.divide(ip, operand)?,
+            // BXL xors B with the literal operand
+            1 => self.registers[B] ^= operand as u64,
+            // BST stores the lowest 3 bits of combo in B
+            2 => self.registers[B] = self.combo(ip, operand)? & 0b111,
+            // JNZ jumps to the literal operand if A is not zero
+            3 => {
+                if self.registers[A] != 0 {
+                    self.ip = operand as usize;
+                }
+            }
+            // BXC xors B with C, the operand is ignored
+            4 => self.registers[B] ^= self.registers[C],
+            // OUT outputs the lowest 3 bits of combo
+            5 => output = Some((self.combo(ip, operand)? & 0b111) as u8),
+            _ => return Err(VmError::UnknownOpcode { ip, opcode }),
+        }
+
+        if let Some(value) = output {
+            self.output.push(value);
+        }
+        if let Some(trace) = &mut self.trace {
+            trace.push(Step {
+                ip,
+                opcode,
+                operand,
+                registers: self.registers,
+                output,
+            });
+        }
+
+        Ok(true)
+    }
+
+    fn combo(&self, ip: usize, operand: u8) -> Result<u64, VmError> {
+        match operand {
+            0..=3 => Ok(operand as u64),
+            4..=6 => Ok(self.registers[operand as usize - 4]),
+            _ => Err(VmError::ReservedOperand { ip }),
+        }
+    }
+
+    fn divide(&self, ip: usize, operand: u8) -> Result<u64, VmError> {
+        let shift = self.combo(ip, operand)?;
+        Ok(self.registers[A]
+            .checked_shr(shift.min(64) as u32)
+            .unwrap_or(0))
+    }
+}
@@ -1,4 +1,4 @@
-use utils::test_solutions;
+use utils::{test_solutions, Cursor, Deque};
 
 fn main() {
     test_solutions(18, &first_part, Some(22), &second_part, Some((6, 1)));
@@ -8,13 +8,15 @@ fn main() {
 
 /// Parses the input (lines of wall coordinates) and returns a vector of wall coordinates.
 fn get_walls(input: &str) -> Vec<(usize, usize)> {
-    input
-        .lines()
-        .map(|line| {
-            let mut coords = line.split(',').map(|x| x.parse::<usize>().unwrap());
-            (coords.next().unwrap(), coords.next().unwrap())
-        })
-        .collect::<Vec<(usize, usize)>>()
+    let mut cursor = Cursor::from(input);
+    let mut walls = vec![];
+    while cursor.skip_until_digit() {
+        walls.push((
+            cursor.next_unsigned().unwrap(),
+            cursor.next_unsigned().unwrap(),
+        ));
+    }
+    walls
 }
 
 /// Uses the wall coordinates to create a grid.
@@ -46,11 +48,10 @@ fn get_weight_grid(grid: &mut Vec<Vec<char>>) -> Vec<Vec<Option<(i32, (usize, us
     let mut weight_grid: Vec<Vec<Option<(i32, (usize, usize))>>> = vec![vec![None; size]; size];
 
     let directions = [(1, 0), (0, 1), (0, -1), (-1, 0)];
-    let mut tiles: Vec<((usize, usize), i32, (usize, usize))> = vec![((0, 0), 0, (0, 0))];
-
-    while !tiles.is_empty() {
-        let (tile_pos, tile_weight, tile_prev) = tiles.remove(0);
+    let mut tiles: Deque<((usize, usize), i32, (usize, usize))> = Deque::new();
+    tiles.push_back(((0, 0), 0, (0, 0)));
 
+    while let Some((tile_pos, tile_weight, tile_prev)) = tiles.pop_front() {
         if weight_grid[tile_pos.1][tile_pos.0].is_some_and(|tile| tile.0 <= tile_weight) {
             continue;
         }
@@ -72,7 +73,7 @@ fn get_weight_grid(grid: &mut Vec<Vec<char>>) -> Vec<Vec<Option<(i32, (usize, us
             }
 
             neighbor_exists = true;
-            tiles.push(((nx as usize, ny as usize), tile_weight + 1, tile_pos));
+            tiles.push_back(((nx as usize, ny as usize), tile_weight + 1, tile_pos));
         }
 
         if !neighbor_exists {
@@ -10,7 +10,7 @@
 //! Additional information about this solution can be found in the module-level documentation.
 
 use rayon::prelude::*;
-use utils::test_solutions;
+use utils::{sections, test_solutions};
 
 fn main() {
     test_solutions(19, &first_part, Some(6), &second_part, Some(16));
@@ -25,7 +25,8 @@ fn main() {
 /// Third index is if the pattern is valid or not. If it is an actual pattern, it is 1 otherwise 0.
 /// Each index holds a pointer to the next element in the array (in this case an index).
 fn parse_input(input: &str) -> (Vec<[usize; 6]>, Vec<Vec<u8>>) {
-    let (patterns, designs) = input.split_once("\n\n").unwrap();
+    let mut sections = sections(input);
+    let (patterns, designs) = (sections.next().unwrap(), sections.next().unwrap());
 
     // Let's start creating the trie. We first loop through each pattern.
     let mut trie: Vec<[usize; 6]> = vec![[0; 6]];
@@ -1,4 +1,4 @@
-use utils::test_solutions;
+use utils::{test_solutions, Cursor};
 
 fn main() {
     test_solutions(2, &first_part, Some(2), &second_part, Some(4));
@@ -6,18 +6,21 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
+/// Parses the input into the levels of every report.
+fn parse_reports(input: &str) -> Vec<Vec<i32>> {
+    let mut cursor = Cursor::from(input);
+    let mut reports = vec![];
+    while !cursor.is_empty() {
+        reports.push(cursor.line_unsigned().unwrap());
+    }
+    reports
+}
+
 /* ------------------- Solutions ------------------- */
 
 fn first_part(input: &str) -> i32 {
-    let reports: Vec<&str> = input.lines().collect();
-
     let mut safe_reports: i32 = 0;
-    for report in reports {
-        let levels: Vec<i32> = report
-            .split_whitespace()
-            .map(|num| num.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>();
-
+    for levels in parse_reports(input) {
         let mut safe: bool = true;
         for i in 1..levels.len() - 1 {
             let prev_dif = levels[i] - levels[i - 1];
@@ -38,15 +41,8 @@ fn first_part(input: &str) -> i32 {
 }
 
 fn second_part(input: &str) -> i32 {
-    let reports: Vec<&str> = input.lines().collect();
-
     let mut safe_reports: i32 = 0;
-    for report in reports {
-        let levels: Vec<i32> = report
-            .split_whitespace()
-            .map(|num| num.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>();
-
+    for levels in parse_reports(input) {
         let mut safe: bool = false;
         for i in 0..levels.len() {
             let mut clone_levels = levels.clone();
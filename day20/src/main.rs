@@ -1,5 +1,5 @@
 use rayon::prelude::*;
-use utils::test_solutions;
+use utils::{test_solutions, Grid, Point, DIRECTIONS, ORIGIN};
 
 fn main() {
     test_solutions(20, &first_part, None, &second_part, None);
@@ -7,67 +7,42 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
-/// Turns 2D grid input into 2D vector of chars.
-fn get_grid(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>()
-}
-
-/// Finds the start and end of the given map.
-fn find_start_end(grid: &Vec<Vec<char>>) -> ((usize, usize), (usize, usize)) {
-    let mut start = (0, 0);
-    let mut end = (0, 0);
-
-    for y in 0..grid.len() {
-        for x in 0..grid[y].len() {
-            if grid[y][x] == 'S' {
-                start = (x, y);
-            }
-            if grid[y][x] == 'E' {
-                end = (x, y);
-            }
-        }
-    }
-
-    (start, end)
+/// Parses the map and finds its start and end.
+fn parse_input(input: &str) -> (Grid<u8>, Point, Point) {
+    let grid = Grid::parse(input);
+    let start = grid.find(b'S').unwrap();
+    let end = grid.find(b'E').unwrap();
+    (grid, start, end)
 }
 
 /// Calculates the weight grid, weight represents the number of tiles it takes to get to that tile.
 fn get_weight_grid_path(
-    grid: &Vec<Vec<char>>,
-    start: (usize, usize),
-    end: (usize, usize),
-) -> (Vec<Vec<u32>>, Vec<((usize, usize), u32)>) {
-    let size = grid.len();
-    let mut weight_grid: Vec<Vec<u32>> = vec![vec![u32::MAX; size]; size];
-
-    let mut path: Vec<((usize, usize), u32)> = vec![(start, 0)];
+    grid: &Grid<u8>,
+    start: Point,
+    end: Point,
+) -> (Grid<u32>, Vec<(Point, u32)>) {
+    let mut weight_grid = grid.clone_with(u32::MAX);
+
+    let mut path: Vec<(Point, u32)> = vec![(start, 0)];
     loop {
-        let (tile_pos, tile_weight) = path.last().unwrap();
-        weight_grid[tile_pos.1][tile_pos.0] = *tile_weight;
+        let (tile_pos, tile_weight) = *path.last().unwrap();
+        weight_grid[tile_pos] = tile_weight;
 
-        if *tile_pos == end {
+        if tile_pos == end {
             break;
         }
 
-        for dir in [(1, 0), (0, 1), (0, -1), (-1, 0)] {
-            let (nx, ny) = (tile_pos.0 as i32 + dir.0, tile_pos.1 as i32 + dir.1);
-            if nx < 0 || ny < 0 || nx >= grid.len() as i32 || ny >= grid[0].len() as i32 {
-                continue;
-            }
-
-            if grid[ny as usize][nx as usize] == '#' {
+        for dir in DIRECTIONS {
+            let next = tile_pos + dir;
+            if !grid.contains(next) || grid[next] == b'#' {
                 continue;
             }
 
-            let nw = weight_grid[ny as usize][nx as usize];
-            if nw != u32::MAX {
+            if weight_grid[next] != u32::MAX {
                 continue;
             }
 
-            path.push(((nx as usize, ny as usize), tile_weight + 1));
+            path.push((next, tile_weight + 1));
             break;
         }
     }
@@ -77,26 +52,19 @@ fn get_weight_grid_path(
 
 /* ------------------- Solutions ------------------- */
 
-#[allow(unused_variables)]
 fn first_part(input: &str) -> u32 {
-    let grid = get_grid(input);
-    let (start, end) = find_start_end(&grid);
+    let (grid, start, end) = parse_input(input);
     let (weight_grid, path) = get_weight_grid_path(&grid, start, end);
 
     let mut total_skips = 0;
-    for i in 0..(path.len() - 5) {
-        let ((x, y), cw) = path[i];
-        for offset in [(2, 0), (0, 2), (-2, 0), (0, -2)] {
-            let (nx, ny) = (x as i32 + offset.0, y as i32 + offset.1);
-            if nx < 0
-                || ny < 0
-                || nx >= weight_grid.len() as i32
-                || ny >= weight_grid[0].len() as i32
-            {
+    for &(pos, cw) in path.iter().take(path.len().saturating_sub(5)) {
+        for dir in DIRECTIONS {
+            let next = pos + dir * 2;
+            if !weight_grid.contains(next) {
                 continue;
             }
 
-            let next_weight = weight_grid[ny as usize][nx as usize];
+            let next_weight = weight_grid[next];
             if next_weight == u32::MAX {
                 continue;
             }
@@ -110,36 +78,31 @@ fn first_part(input: &str) -> u32 {
     total_skips
 }
 
-#[allow(unused_variables)]
 fn second_part(input: &str) -> u32 {
-    let grid = get_grid(input);
-    let (start, end) = find_start_end(&grid);
+    let (grid, start, end) = parse_input(input);
     let (weight_grid, path) = get_weight_grid_path(&grid, start, end);
 
-    let skips_per_tile = path.par_iter().map(|((x, y), cw)| {
+    let skips_per_tile = path.par_iter().map(|&(pos, cw)| {
         let mut skips = 0;
         for ox in -20_i32..21 {
             for oy in (ox.abs() - 20)..(21 - ox.abs()) {
-                let dist = ox.abs() + oy.abs();
+                let offset = Point::new(ox, oy);
+                let dist = offset.manhattan(ORIGIN);
                 if dist < 2 {
                     continue;
                 }
 
-                let (nx, ny) = (*x as i32 + ox, *y as i32 + oy);
-                if nx < 0
-                    || ny < 0
-                    || nx >= weight_grid.len() as i32
-                    || ny >= weight_grid[0].len() as i32
-                {
+                let next = pos + offset;
+                if !weight_grid.contains(next) {
                     continue;
                 }
 
-                let next_weight = weight_grid[ny as usize][nx as usize];
+                let next_weight = weight_grid[next];
                 if next_weight == u32::MAX {
                     continue;
                 }
 
-                if next_weight > *cw && next_weight - cw >= (100 + dist) as u32 {
+                if next_weight > cw && next_weight - cw >= (100 + dist) as u32 {
                     skips += 1;
                 }
             }
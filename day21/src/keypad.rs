@@ -0,0 +1,211 @@
+//! Keypads of day 21 and the cost of typing on them through a chain of robots.
+//!
+//! A `Keypad` is parsed from an ASCII drawing like `"789\n456\n123\n 0A"`, spaces are gaps that the arm must never
+//! point at. Keys get ids in reading order, the number of keys is part of the type so cost tables are plain arrays.
+//! `Costs` of a layer are the presses a human needs to move the arm from one key to another and press it.
+//! A `Layer` tries every shortest path between two keys that avoids the gaps and keeps the cheapest one,
+//! so any connected layout works, not only the two keypads of the puzzle.
+//! `cost_table` stacks the same keypad layer by layer and keeps the costs of every depth, so one call
+//! answers every number of robots up to it. Costs grow about 2.5 times per layer, they are `u128` and
+//! every sum is checked, so the table stops at the last depth that fits instead of returning wrong costs.
+
+use utils::{Point, BOTTOM, LEFT, RIGHT, TOP};
+
+/// The arm moves and the directional keys that move it.
+const DIRECTIONS: [(Point, char); 4] = [(RIGHT, '>'), (BOTTOM, 'v'), (LEFT, '<'), (TOP, '^')];
+
+/// `costs[from][to]` is the cost of moving from key `from` to key `to` and pressing it.
+pub type Costs<const N: usize> = [[u128; N]; N];
+
+#[derive(Clone, Debug)]
+pub struct Keypad<const N: usize> {
+    keys: [char; N],
+    positions: [Point; N],
+}
+
+impl<const N: usize> Keypad<N> {
+    /// Parses the drawing, every character except a space is a key and there have to be exactly `N` of them.
+    /// The arm has to be able to reach every key from every other one without pointing at a gap.
+    pub fn parse(drawing: &str) -> Self {
+        let mut keys = vec![];
+        let mut positions = vec![];
+        for (y, line) in drawing.lines().enumerate() {
+            for (x, key) in line.chars().enumerate().filter(|&(_, key)| key != ' ') {
+                assert!(
+                    !keys.contains(&key),
+                    "Key {key} appears twice on the keypad"
+                );
+                keys.push(key);
+                positions.push(Point::new(x as i32, y as i32));
+            }
+        }
+
+        let count = keys.len();
+        let keypad = Keypad {
+            keys: keys
+                .try_into()
+                .unwrap_or_else(|_| panic!("The keypad has {count} keys instead of {N}")),
+            positions: positions.try_into().unwrap(),
+        };
+        assert!(
+            N == 0
+                || keypad
+                    .distances(0)
+                    .iter()
+                    .all(|&distance| distance != usize::MAX),
+            "The keys of the keypad are not all connected"
+        );
+        keypad
+    }
+
+    pub fn id(&self, key: char) -> usize {
+        self.keys
+            .iter()
+            .position(|&k| k == key)
+            .unwrap_or_else(|| panic!("There is no key {key} on the keypad"))
+    }
+
+    /// Returns the key at the point, or `None` for a gap or a point outside of the keypad.
+    fn key_at(&self, point: Point) -> Option<usize> {
+        self.positions
+            .iter()
+            .position(|&position| position == point)
+    }
+
+    /// Returns the number of arm moves from every key to `to`, `usize::MAX` for keys that cannot reach it.
+    fn distances(&self, to: usize) -> [usize; N] {
+        let mut distances = [usize::MAX; N];
+        distances[to] = 0;
+        let mut frontier = vec![to];
+        while !frontier.is_empty() {
+            let mut next = vec![];
+            for key in frontier {
+                for (direction, _) in DIRECTIONS {
+                    let Some(neighbour) = self.key_at(self.positions[key] + direction) else {
+                        continue;
+                    };
+                    if distances[neighbour] == usize::MAX {
+                        distances[neighbour] = distances[key] + 1;
+                        next.push(neighbour);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        distances
+    }
+
+    /// Returns the directional key sequences of every shortest move from one key to another that avoids the gaps,
+    /// each followed by the press of `A`.
+    pub fn moves(&self, from: usize, to: usize) -> Vec<Vec<char>> {
+        let distances = self.distances(to);
+        let mut moves = vec![];
+        self.collect_moves(from, &distances, &mut vec![], &mut moves);
+        moves
+    }
+
+    /// Extends `path` by every step that gets one move closer to the target, until it is reached.
+    fn collect_moves(
+        &self,
+        key: usize,
+        distances: &[usize; N],
+        path: &mut Vec<char>,
+        moves: &mut Vec<Vec<char>>,
+    ) {
+        if distances[key] == 0 {
+            moves.push(path.iter().copied().chain(['A']).collect());
+            return;
+        }
+
+        for (direction, arrow) in DIRECTIONS {
+            let Some(next) = self.key_at(self.positions[key] + direction) else {
+                continue;
+            };
+            if distances[next] + 1 == distances[key] {
+                path.push(arrow);
+                self.collect_moves(next, distances, path, moves);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Returns the cost of typing the keys on a keypad with the given costs, the arm starts at `A`.
+/// Returns `None` if the cost does not fit into a `u128`.
+pub fn sequence_cost<const N: usize>(
+    keypad: &Keypad<N>,
+    costs: &Costs<N>,
+    keys: impl IntoIterator<Item = char>,
+) -> Option<u128> {
+    let mut from = keypad.id('A');
+    let mut total: u128 = 0;
+    for key in keys {
+        let to = keypad.id(key);
+        total = total.checked_add(costs[from][to])?;
+        from = to;
+    }
+    Some(total)
+}
+
+/// A keypad whose arm is moved by typing on `controller`, with the key sequences of every move stored
+/// as controller key ids, so computing the costs for another controller cost table is only additions.
+pub struct Layer<const N: usize, const C: usize> {
+    start: usize,
+    moves: [[Vec<Vec<usize>>; N]; N],
+}
+
+impl<const N: usize, const C: usize> Layer<N, C> {
+    /// The controller has to hold the directional keys.
+    pub fn new(keypad: &Keypad<N>, controller: &Keypad<C>) -> Self {
+        let moves = std::array::from_fn(|from| {
+            std::array::from_fn(|to| {
+                keypad
+                    .moves(from, to)
+                    .into_iter()
+                    .map(|keys| keys.into_iter().map(|key| controller.id(key)).collect())
+                    .collect()
+            })
+        });
+        Layer {
+            start: controller.id('A'),
+            moves,
+        }
+    }
+
+    /// Returns the costs of the keypad given the costs of the controller, which is back at `A` after every press.
+    /// Every move takes the cheapest of its shortest paths, `None` if none of them fits into a `u128`.
+    pub fn costs(&self, controller_costs: &Costs<C>) -> Option<Costs<N>> {
+        let mut costs = [[0; N]; N];
+        for (from, row) in costs.iter_mut().enumerate() {
+            for (to, cost) in row.iter_mut().enumerate() {
+                *cost = self.moves[from][to]
+                    .iter()
+                    .filter_map(|keys| {
+                        let mut previous = self.start;
+                        keys.iter().try_fold(0, |total: u128, &key| {
+                            let cost = controller_costs[previous][key];
+                            previous = key;
+                            total.checked_add(cost)
+                        })
+                    })
+                    .min()?;
+            }
+        }
+        Some(costs)
+    }
+}
+
+/// Returns the costs of the keypad for every depth up to `depth`, where depth `d` is the keypad moved by
+/// a robot typing on depth `d - 1`. Depth 0 is pressed by a human, so every press costs 1.
+/// The table ends early at the last depth whose costs fit into a `u128`.
+pub fn cost_table<const N: usize>(keypad: &Keypad<N>, depth: usize) -> Vec<Costs<N>> {
+    let layer = Layer::new(keypad, keypad);
+    let mut table = vec![[[1; N]; N]];
+    while table.len() <= depth {
+        match layer.costs(table.last().unwrap()) {
+            Some(costs) => table.push(costs),
+            None => break,
+        }
+    }
+    table
+}
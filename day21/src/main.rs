@@ -1,7 +1,15 @@
-use std::collections::HashMap;
-use utils::test_solutions;
+mod keypad;
+
+use keypad::{cost_table, sequence_cost, Costs, Keypad, Layer};
+use std::sync::OnceLock;
+use utils::{get_input, test_solutions};
 
 fn main() {
+    if let Ok(depth) = std::env::var("DAY21_CURVE") {
+        let depth = depth.parse().expect("DAY21_CURVE has to be a depth");
+        print_curve(&get_input(Some(21)), depth);
+    }
+
     test_solutions(
         21,
         &first_part,
@@ -13,181 +21,71 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
-/// Parses and turns the given input into a vector of numpad inputs ands scores.
-fn get_numpad_inputs(input: &str) -> Vec<(Vec<char>, u64)> {
+const NUMERIC_KEYPAD: &str = "789\n456\n123\n 0A";
+const DIRECTIONAL_KEYPAD: &str = " ^A\n<v>";
+
+/// The most robots a part asks for.
+const ROBOTS: usize = 25;
+
+/// Parses and turns the given input into a vector of codes and their numeric parts.
+fn get_numpad_inputs(input: &str) -> Vec<(&str, u128)> {
     input
         .lines()
-        .map(|line| {
-            (
-                line.chars().collect::<Vec<char>>(),
-                line.split_at(line.len() - 1).0.parse::<u64>().unwrap(),
-            )
-        })
-        .collect::<Vec<(Vec<char>, u64)>>()
+        .map(|line| (line, line[..line.len() - 1].parse::<u128>().unwrap()))
+        .collect()
 }
 
-/// Returns the movements you have to make to get from one number to another.
-fn get_numpad_movement(input_moves: &Vec<char>) -> Vec<char> {
-    let mut moves: Vec<char> = vec![];
-    let positions: HashMap<char, (i32, i32)> = HashMap::from([
-        ('A', (2, 3)),
-        ('0', (1, 3)),
-        ('1', (0, 2)),
-        ('2', (1, 2)),
-        ('3', (2, 2)),
-        ('4', (0, 1)),
-        ('5', (1, 1)),
-        ('6', (2, 1)),
-        ('7', (0, 0)),
-        ('8', (1, 0)),
-        ('9', (2, 0)),
-    ]);
-
-    let mut from = 'A';
-    for i in 0..input_moves.len() {
-        let to = input_moves[i];
-
-        let from_pos = positions[&from];
-        let to_pos = positions[&to];
-
-        from = to;
-
-        let dx = to_pos.0 - from_pos.0;
-        let dy = to_pos.1 - from_pos.1;
-
-        let mut next_moves = vec![];
-        if dx < 0 {
-            next_moves.extend(vec!['<'; dx.abs() as usize]);
-        }
-        if dy < 0 {
-            next_moves.extend(vec!['^'; dy.abs() as usize]);
-        }
-        if dy > 0 {
-            next_moves.extend(vec!['v'; dy as usize]);
-        }
-        if dx > 0 {
-            next_moves.extend(vec!['>'; dx as usize]);
-        }
-
-        if to_pos.0 == 0 && from_pos.1 == 3 {
-            next_moves.reverse();
-        }
-        if from_pos.0 == 0 && to_pos.1 == 3 {
-            next_moves.reverse();
-        }
-
-        moves.extend(next_moves);
-        moves.push('A');
-    }
-    moves
+/// Returns the costs of the directional keypad for every number of robots up to `ROBOTS`, built on the first
+/// call and shared by both parts.
+fn directional_costs() -> &'static [Costs<5>] {
+    static TABLE: OnceLock<Vec<Costs<5>>> = OnceLock::new();
+    TABLE.get_or_init(|| cost_table(&Keypad::parse(DIRECTIONAL_KEYPAD), ROBOTS))
 }
 
-/// Returns the movements you have to make to get from one move character to another.
-fn get_moves_needed(from: char, to: char) -> Vec<char> {
-    let mut moves_needed = vec![];
-    match (from, to) {
-        ('A', '^') => moves_needed.extend(vec!['<']),
-        ('A', '>') => moves_needed.extend(vec!['v']),
-        ('A', 'v') => moves_needed.extend(vec!['<', 'v']),
-        ('A', '<') => moves_needed.extend(vec!['v', '<', '<']),
-        ('^', 'A') => moves_needed.extend(vec!['>']),
-        ('^', '>') => moves_needed.extend(vec!['v', '>']),
-        ('^', 'v') => moves_needed.extend(vec!['v']),
-        ('^', '<') => moves_needed.extend(vec!['v', '<']),
-        ('>', 'A') => moves_needed.extend(vec!['^']),
-        ('>', '^') => moves_needed.extend(vec!['<', '^']),
-        ('>', 'v') => moves_needed.extend(vec!['<']),
-        ('>', '<') => moves_needed.extend(vec!['<', '<']),
-        ('v', 'A') => moves_needed.extend(vec!['^', '>']),
-        ('v', '^') => moves_needed.extend(vec!['^']),
-        ('v', '>') => moves_needed.extend(vec!['>']),
-        ('v', '<') => moves_needed.extend(vec!['<']),
-        ('<', 'A') => moves_needed.extend(vec!['>', '>', '^']),
-        ('<', '^') => moves_needed.extend(vec!['>', '^']),
-        ('<', '>') => moves_needed.extend(vec!['>', '>']),
-        ('<', 'v') => moves_needed.extend(vec!['>']),
-        _ => (),
-    }
-
-    moves_needed.push('A');
-    moves_needed
+/// Returns the total complexity of the codes when the robot typing on the numeric keypad has the given
+/// directional costs, or `None` if it does not fit into a `u128`.
+fn total_complexity(input: &str, directional_costs: &Costs<5>) -> Option<u128> {
+    let numeric: Keypad<11> = Keypad::parse(NUMERIC_KEYPAD);
+    let directional: Keypad<5> = Keypad::parse(DIRECTIONAL_KEYPAD);
+    let costs = Layer::new(&numeric, &directional).costs(directional_costs)?;
+
+    get_numpad_inputs(input)
+        .into_iter()
+        .try_fold(0, |total: u128, (code, number)| {
+            let cost = sequence_cost(&numeric, &costs, code.chars())?;
+            total.checked_add(cost.checked_mul(number)?)
+        })
 }
 
-/// Calculate the cache for the given robot
-fn calculate_cache(robot: usize) -> HashMap<(char, char), u64> {
-    let all_characters = vec!['A', '^', '>', '<', 'v'];
-    let mut next_cache = HashMap::new();
-
-    if robot == 1 {
-        for from in all_characters.iter() {
-            for to in all_characters.iter() {
-                next_cache.insert((*from, *to), get_moves_needed(*from, *to).len() as u64);
-            }
-        }
-        return next_cache;
-    }
-
-    let prev_cache = calculate_cache(robot - 1);
-    for from in all_characters.iter() {
-        for to in all_characters.iter() {
-            let moves_needed = get_moves_needed(*from, *to);
-            let mut score = 0;
-            let mut prev_from = 'A';
-            for movement in moves_needed.iter() {
-                let prev_score = prev_cache.get(&(prev_from, *movement)).unwrap();
-                prev_from = *movement;
-                score += prev_score;
-            }
-
-            next_cache.insert((*from, *to), score);
+/// Prints the total complexity for every number of robots up to `depth`, together with how much it grew
+/// from the depth before, and the first depth that does not fit into a `u128`.
+fn print_curve(input: &str, depth: usize) {
+    let table = cost_table(&Keypad::parse(DIRECTIONAL_KEYPAD), depth);
+    let mut previous = None;
+    for robots in 0..=depth {
+        let Some(complexity) = table
+            .get(robots)
+            .and_then(|costs| total_complexity(input, costs))
+        else {
+            println!("{robots:>3} robots: does not fit into a u128");
+            return;
+        };
+        if let Some(previous) = previous {
+            let growth = complexity as f64 / previous as f64;
+            println!("{robots:>3} robots: {complexity:>39} (x{growth:.3})");
         }
+        previous = Some(complexity);
     }
-
-    next_cache
 }
 
 /* ------------------- Solutions ------------------- */
 
-#[allow(unused_variables)]
-fn first_part(input: &str) -> u64 {
-    let numpad_inputs = get_numpad_inputs(input);
-
-    let cache = calculate_cache(2);
-    let mut total_score = 0;
-    for (numpad, score) in numpad_inputs {
-        let numpad_moves = get_numpad_movement(&numpad);
-
-        let mut from = 'A';
-        let mut check_score = 0;
-        for to in numpad_moves {
-            let score = cache.get(&(from, to)).unwrap();
-            from = to;
-            check_score += score;
-        }
-        total_score += score * check_score;
-    }
-
-    total_score
+fn first_part(input: &str) -> u128 {
+    total_complexity(input, &directional_costs()[2])
+        .expect("The complexity does not fit into a u128")
 }
 
-#[allow(unused_variables)]
-fn second_part(input: &str) -> u64 {
-    let numpad_inputs = get_numpad_inputs(input);
-
-    let cache = calculate_cache(25);
-    let mut total_score = 0;
-    for (numpad, score) in numpad_inputs {
-        let numpad_moves = get_numpad_movement(&numpad);
-
-        let mut from = 'A';
-        let mut check_score = 0;
-        for to in numpad_moves {
-            let score = cache.get(&(from, to)).unwrap();
-            from = to;
-            check_score += score;
-        }
-        total_score += score * check_score;
-    }
-
-    total_score
+fn second_part(input: &str) -> u128 {
+    total_complexity(input, &directional_costs()[25])
+        .expect("The complexity does not fit into a u128")
 }
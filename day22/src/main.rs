@@ -16,7 +16,7 @@
 
 use rayon::prelude::*;
 use std::sync::Mutex;
-use utils::test_solutions;
+use utils::{test_solutions, Cursor};
 use wide::u32x8;
 
 fn main() {
@@ -27,10 +27,12 @@ fn main() {
 
 /// Parses and turns the given input into a vector of numbers.
 fn get_numbers(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>()
+    let mut cursor = Cursor::from(input);
+    let mut numbers = vec![];
+    while cursor.skip_until_digit() {
+        numbers.push(cursor.next_unsigned().unwrap());
+    }
+    numbers
 }
 
 /// Calculates the next random number using the XOR-Shift algorithm.
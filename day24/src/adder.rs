@@ -0,0 +1,208 @@
+//! Verifies that a `Circuit` is a ripple-carry adder of its `x` and `y` buses into `z`, and repairs it.
+//!
+//! Bit `i` of such an adder is a full adder, `z_i = (x_i ^ y_i) ^ c_i` and `c_i+1 = (x_i & y_i) | ((x_i ^ y_i) & c_i)`,
+//! where bit 0 is a half adder and the last `z` wire is the final carry.
+//! `check_structure` looks at every gate on its own and reports the ones that cannot be a part of such a circuit.
+//! `find_swaps` tries the smallest sets of swaps between the reported wires, keeping the first one that passes
+//! both the structural check and a simulation of the circuit on edge cases and random `x`/`y` pairs.
+
+use crate::circuit::{Circuit, Op};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// Swaps are only searched among this many suspicious wires, above that the circuit is not a broken adder.
+const MAX_CANDIDATES: usize = 16;
+
+/// Number of random `x`/`y` pairs a repaired circuit has to add correctly.
+const SAMPLES: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// A XOR gate drives the wrong wire, the sum of bits has to drive a `z` wire and `x ^ y` the sum XOR.
+    WrongXorOutput,
+    /// A carry gate does not feed the next part of the carry chain.
+    MisroutedCarry,
+    /// A `z` wire is not driven by the sum XOR, or the last one by the final carry OR.
+    SwappedZ,
+}
+
+/// A wire whose gate breaks the adder structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Issue {
+    pub wire: usize,
+    pub fault: Fault,
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Fault::WrongXorOutput => "wrong XOR output",
+            Fault::MisroutedCarry => "misrouted carry",
+            Fault::SwappedZ => "swapped z wire",
+        };
+        write!(f, "{description}")
+    }
+}
+
+/// Reports every gate that does not fit into a ripple-carry adder, at most one issue per wire.
+pub fn check_structure(circuit: &Circuit) -> Vec<Issue> {
+    let consumers = circuit.consumers();
+    let (x, y, z) = (circuit.bus('x'), circuit.bus('y'), circuit.bus('z'));
+    let inputs: HashSet<usize> = x.iter().chain(&y).copied().collect();
+    let outputs: HashSet<usize> = z.iter().copied().collect();
+    let last_z = z.last().copied();
+    let is_input = |id: usize| inputs.contains(&id);
+    let is_first = |id: usize| x.first() == Some(&id) || y.first() == Some(&id);
+    let feeds = |id: usize, op: Op| {
+        consumers[id]
+            .iter()
+            .any(|&consumer| circuit.gate(consumer).is_some_and(|gate| gate.op == op))
+    };
+
+    let mut issues = vec![];
+    for (wire, gate) in circuit.gates() {
+        let from_inputs = gate.inputs.iter().all(|&input| is_input(input));
+        let first_bit = gate.inputs.iter().all(|&input| is_first(input));
+
+        let fault = if outputs.contains(&wire) {
+            match gate.op {
+                _ if Some(wire) == last_z && !first_bit => {
+                    (gate.op != Op::Or).then_some(Fault::SwappedZ)
+                }
+                Op::Xor if from_inputs && !first_bit => Some(Fault::WrongXorOutput),
+                Op::Xor => None,
+                _ => Some(Fault::SwappedZ),
+            }
+        } else {
+            match gate.op {
+                // The sum XOR has to drive a z wire and x ^ y has to feed it
+                Op::Xor if !from_inputs || first_bit => Some(Fault::WrongXorOutput),
+                Op::Xor if !feeds(wire, Op::Xor) => Some(Fault::WrongXorOutput),
+                // Both ANDs of a bit are combined by the carry OR, except for the half adder of bit 0
+                Op::And if !first_bit && !feeds(wire, Op::Or) => Some(Fault::MisroutedCarry),
+                // Carries have to feed the sum XOR of the next bit
+                Op::And if first_bit && !feeds(wire, Op::Xor) => Some(Fault::MisroutedCarry),
+                Op::Or if !feeds(wire, Op::Xor) => Some(Fault::MisroutedCarry),
+                _ => None,
+            }
+        };
+
+        if let Some(fault) = fault {
+            issues.push(Issue { wire, fault });
+        }
+    }
+
+    issues
+}
+
+/// Returns whether the circuit adds its `x` and `y` buses, checked on every single bit, every carry
+/// and `SAMPLES` random pairs.
+pub fn is_adder(circuit: &Circuit) -> bool {
+    let width = circuit.bus('x').len();
+    if width == 0
+        || width > 127
+        || circuit.bus('y').len() != width
+        || circuit.bus('z').len() != width + 1
+    {
+        return false;
+    }
+
+    let mask = (1u128 << width) - 1;
+    let mut random = XorShift(0x2024_1224);
+    let single_bits = (0..width)
+        .map(|bit| 1u128 << bit)
+        .flat_map(|bit| [(bit, 0), (0, bit), (bit, bit)]);
+    let randoms = (0..SAMPLES).map(|_| (random.next() & mask, random.next() & mask));
+    let mut pairs = [(0, 0), (mask, 1), (mask, mask)]
+        .into_iter()
+        .chain(single_bits)
+        .chain(randoms);
+
+    let mut circuit = circuit.clone();
+    pairs.all(|(x, y)| circuit.compute(x, y) == Ok(x + y))
+}
+
+/// Finds the fewest swaps of gate outputs that turn the circuit into an adder, as pairs of wire ids.
+/// Returns `None` if the gates cannot form an adder or no swaps among the suspicious wires repair it.
+pub fn find_swaps(circuit: &Circuit) -> Option<Vec<(usize, usize)>> {
+    // Swapping outputs never changes which gates there are, a ripple-carry adder of n bits
+    // has 2n - 1 XOR and AND gates and n - 1 OR gates.
+    let width = circuit.bus('x').len();
+    let count = |op: Op| circuit.gates().filter(|(_, gate)| gate.op == op).count();
+    if width == 0
+        || count(Op::Xor) != 2 * width - 1
+        || count(Op::And) != 2 * width - 1
+        || count(Op::Or) != width - 1
+    {
+        return None;
+    }
+
+    let mut candidates: Vec<usize> = check_structure(circuit)
+        .iter()
+        .map(|issue| issue.wire)
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+    if candidates.len() > MAX_CANDIDATES {
+        return None;
+    }
+
+    let mut swaps = vec![];
+    let repairs = |swaps: &[(usize, usize)]| {
+        let mut repaired = circuit.clone();
+        for &(a, b) in swaps {
+            repaired.swap(a, b);
+        }
+        check_structure(&repaired).is_empty() && is_adder(&repaired)
+    };
+
+    (0..=candidates.len() / 2)
+        .find(|&pairs| choose_pairs(&candidates, pairs, &mut swaps, &repairs))
+        .map(|_| swaps)
+}
+
+/// Tries every set of `pairs` disjoint pairs from the candidates, stops at the first one `check` accepts
+/// and leaves it in `swaps`.
+fn choose_pairs(
+    candidates: &[usize],
+    pairs: usize,
+    swaps: &mut Vec<(usize, usize)>,
+    check: &impl Fn(&[(usize, usize)]) -> bool,
+) -> bool {
+    if pairs == 0 {
+        return check(swaps);
+    }
+    let [first, rest @ ..] = candidates else {
+        return false;
+    };
+    if candidates.len() < pairs * 2 {
+        return false;
+    }
+
+    for (i, &second) in rest.iter().enumerate() {
+        let remaining: Vec<usize> = rest[..i].iter().chain(&rest[i + 1..]).copied().collect();
+        swaps.push((*first, second));
+        if choose_pairs(&remaining, pairs - 1, swaps, check) {
+            return true;
+        }
+        swaps.pop();
+    }
+
+    // The first candidate can also stay where it is
+    choose_pairs(rest, pairs, swaps, check)
+}
+
+/// A small deterministic XOR-Shift generator for the random samples, so every run checks the same pairs.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u128 {
+        let mut half = || {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 as u128
+        };
+        half() << 64 | half()
+    }
+}
@@ -0,0 +1,273 @@
+//! The logic gate circuit of day 24.
+//!
+//! Wire names are interned into ids in the order they first appear, so names of any length and alphabet work.
+//! Every wire is either driven by one gate or is an input with a value from the first section of the input.
+//! `evaluate` computes every wire in topological order and reports cycles and wires without a value as errors.
+//! The `x`, `y` and `z` wires form buses, `x00` is the lowest bit, their width is whatever the input has.
+//! Bus values are read and written as `u128`, so `set`, `get` and `compute` only work on buses of up to 128 bits.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use utils::{parse, sections, PatternError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl Op {
+    pub fn apply(self, left: bool, right: bool) -> bool {
+        match self {
+            Op::And => left & right,
+            Op::Or => left | right,
+            Op::Xor => left ^ right,
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AND" => Ok(Op::And),
+            "OR" => Ok(Op::Or),
+            "XOR" => Ok(Op::Xor),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A gate, the inputs are wire ids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gate {
+    pub op: Op,
+    pub inputs: [usize; 2],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CircuitError {
+    /// The wire is part of a cycle of gates.
+    Cycle(String),
+    /// The wire has neither a gate nor an input value.
+    Undriven(String),
+    /// The wire has more than one gate or input value driving it.
+    MultipleDrivers(String),
+    /// The input does not have the format of the puzzle.
+    Parse(PatternError),
+}
+
+impl From<PatternError> for CircuitError {
+    fn from(error: PatternError) -> Self {
+        CircuitError::Parse(error)
+    }
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Cycle(wire) => write!(f, "Wire {wire} is part of a cycle"),
+            CircuitError::Undriven(wire) => write!(f, "Wire {wire} has no gate and no value"),
+            CircuitError::MultipleDrivers(wire) => {
+                write!(f, "Wire {wire} has more than one gate or value")
+            }
+            CircuitError::Parse(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for CircuitError {}
+
+#[derive(Clone, Debug, Default)]
+pub struct Circuit {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    gates: Vec<Option<Gate>>,
+    values: Vec<Option<bool>>,
+}
+
+impl Circuit {
+    /// Parses the `x00: 1` input values and the `x00 AND y00 -> z00` gates.
+    /// Every wire can have at most one gate or input value.
+    pub fn parse(input: &str) -> Result<Self, CircuitError> {
+        let mut sections = sections(input);
+        let (inputs, gates) = (
+            sections.next().unwrap_or_default(),
+            sections.next().unwrap_or_default(),
+        );
+
+        let mut circuit = Circuit::default();
+        for (wire, value) in parse!("{}: {}").parse_lines_in::<(String, u8)>(input, inputs)? {
+            let id = circuit.intern(&wire);
+            if circuit.values[id].is_some() {
+                return Err(CircuitError::MultipleDrivers(wire));
+            }
+            circuit.values[id] = Some(value != 0);
+        }
+        for (left, op, right, output) in
+            parse!("{} {} {} -> {}").parse_lines_in::<(String, Op, String, String)>(input, gates)?
+        {
+            let inputs = [circuit.intern(&left), circuit.intern(&right)];
+            let id = circuit.intern(&output);
+            if circuit.values[id].is_some() || circuit.gates[id].is_some() {
+                return Err(CircuitError::MultipleDrivers(output));
+            }
+            circuit.gates[id] = Some(Gate { op, inputs });
+        }
+
+        Ok(circuit)
+    }
+
+    /// Returns the id of the wire, adding it if it is new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.gates.push(None);
+        self.values.push(None);
+        id
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// Returns the number of wires.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn gate(&self, id: usize) -> Option<Gate> {
+        self.gates[id]
+    }
+
+    /// Returns every gate with the id of the wire it drives.
+    pub fn gates(&self) -> impl Iterator<Item = (usize, Gate)> + '_ {
+        self.gates
+            .iter()
+            .enumerate()
+            .filter_map(|(id, gate)| gate.map(|gate| (id, gate)))
+    }
+
+    /// Returns the gates reading every wire, as the ids of the wires they drive.
+    pub fn consumers(&self) -> Vec<Vec<usize>> {
+        let mut consumers = vec![vec![]; self.len()];
+        for (id, gate) in self.gates() {
+            for input in gate.inputs {
+                consumers[input].push(id);
+            }
+        }
+        consumers
+    }
+
+    /// Swaps the gates driving the two wires.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.gates.swap(a, b);
+    }
+
+    /// Returns the ids of the wires named `prefix` followed by a number, ordered from the lowest bit.
+    pub fn bus(&self, prefix: char) -> Vec<usize> {
+        let mut bits: Vec<(u32, usize)> = self
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(id, name)| Some((name.strip_prefix(prefix)?.parse().ok()?, id)))
+            .collect();
+        bits.sort_unstable();
+        bits.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Sets the input wires of the bus to the bits of `value`, which has to fit into it.
+    pub fn set(&mut self, prefix: char, value: u128) {
+        let bus = self.bus(prefix);
+        assert!(
+            bus.len() >= 128 || value >> bus.len() == 0,
+            "{value} does not fit into the {} bits of bus {prefix}",
+            bus.len()
+        );
+
+        for (bit, id) in bus.into_iter().enumerate() {
+            self.values[id] = Some(bit < 128 && (value >> bit) & 1 == 1);
+        }
+    }
+
+    /// Reads the bus out of evaluated wire values, it has to have at most 128 bits.
+    pub fn get(&self, values: &[bool], prefix: char) -> u128 {
+        let bus = self.bus(prefix);
+        assert!(
+            bus.len() <= 128,
+            "Bus {prefix} has {} bits, more than fit into a u128",
+            bus.len()
+        );
+        bus.into_iter()
+            .enumerate()
+            .map(|(bit, id)| (values[id] as u128) << bit)
+            .sum()
+    }
+
+    /// Returns the wires in an order where every gate comes after its inputs, using Kahn's algorithm.
+    pub fn topological_order(&self) -> Result<Vec<usize>, CircuitError> {
+        let outputs = self.consumers();
+        let mut pending = vec![0; self.len()];
+        for (id, _) in self.gates() {
+            pending[id] = 2;
+        }
+
+        let mut order: Vec<usize> = (0..self.len()).filter(|&id| pending[id] == 0).collect();
+        let mut next = 0;
+        while let Some(&id) = order.get(next) {
+            next += 1;
+            for &output in &outputs[id] {
+                pending[output] -= 1;
+                if pending[output] == 0 {
+                    order.push(output);
+                }
+            }
+        }
+
+        match pending.iter().position(|&count| count > 0) {
+            Some(id) => Err(CircuitError::Cycle(self.names[id].clone())),
+            None => Ok(order),
+        }
+    }
+
+    /// Computes the value of every wire, indexed by id.
+    pub fn evaluate(&self) -> Result<Vec<bool>, CircuitError> {
+        let mut values = vec![false; self.len()];
+        for id in self.topological_order()? {
+            values[id] = match self.gates[id] {
+                Some(Gate { op, inputs }) => op.apply(values[inputs[0]], values[inputs[1]]),
+                None => {
+                    self.values[id].ok_or_else(|| CircuitError::Undriven(self.names[id].clone()))?
+                }
+            };
+        }
+        Ok(values)
+    }
+
+    /// Sets the `x` and `y` buses, evaluates the circuit and returns the `z` bus.
+    pub fn compute(&mut self, x: u128, y: u128) -> Result<u128, CircuitError> {
+        self.set('x', x);
+        self.set('y', y);
+        Ok(self.get(&self.evaluate()?, 'z'))
+    }
+}
@@ -0,0 +1,143 @@
+//! Exports a `Circuit` for outside tools, as a Graphviz DOT graph and as a structural Verilog module.
+//!
+//! In the DOT graph every wire is a node, gate outputs are filled with the colour of their op and point back to
+//! their inputs, so `dot -Tsvg` draws the adder bit by bit. Highlighted wires, like the ones the adder check
+//! reports or the ones part 2 swaps, get a thick red border.
+//! The Verilog module uses one gate primitive per gate, the undriven wires are inputs and the `z` wires outputs.
+
+use crate::circuit::{Circuit, Op};
+use std::fmt::Write;
+
+/// The reserved keywords of Verilog (IEEE 1364-2005), wires with these names have to be escaped.
+const KEYWORDS: &str = "always and assign automatic begin buf bufif0 bufif1 case casex casez cell cmos config deassign \
+     default defparam design disable edge else end endcase endconfig endfunction endgenerate \
+     endmodule endprimitive endspecify endtable endtask event for force forever fork function \
+     generate genvar highz0 highz1 if ifnone incdir include initial inout input instance integer \
+     join large liblist library localparam macromodule medium module nand negedge nmos nor \
+     noshowcancelled not notif0 notif1 or output parameter pmos posedge primitive pull0 pull1 \
+     pulldown pullup pulsestyle_ondetect pulsestyle_onevent rcmos real realtime reg release repeat \
+     rnmos rpmos rtran rtranif0 rtranif1 scalared showcancelled signed small specify specparam \
+     strong0 strong1 supply0 supply1 table task time tran tranif0 tranif1 tri tri0 tri1 triand trior \
+     trireg unsigned use uwire vectored wait wand weak0 weak1 while wire wor xnor xor";
+
+fn colour(op: Op) -> &'static str {
+    match op {
+        Op::And => "lightblue",
+        Op::Or => "palegreen",
+        Op::Xor => "orange",
+    }
+}
+
+/// Returns the circuit as a DOT digraph, the highlighted wires are drawn with a red border.
+pub fn to_dot(circuit: &Circuit, highlighted: &[usize]) -> String {
+    let mut dot = String::from(
+        "digraph circuit {\n    rankdir=LR;\n    node [style=filled, fillcolor=white];\n",
+    );
+    let outputs = circuit.bus('z');
+
+    for id in 0..circuit.len() {
+        let name = circuit.name(id);
+        let mut attributes = match circuit.gate(id) {
+            Some(gate) => format!(
+                "label=\"{name}\\n{}\", fillcolor={}",
+                gate.op,
+                colour(gate.op)
+            ),
+            None => format!("label=\"{name}\", shape=box"),
+        };
+        if outputs.contains(&id) {
+            attributes += ", shape=doubleoctagon";
+        }
+        if highlighted.contains(&id) {
+            attributes += ", color=red, penwidth=3";
+        }
+        writeln!(dot, "    \"{name}\" [{attributes}];").unwrap();
+    }
+
+    for (id, gate) in circuit.gates() {
+        for input in gate.inputs {
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\";",
+                circuit.name(input),
+                circuit.name(id)
+            )
+            .unwrap();
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Escapes names that are not plain Verilog identifiers, an escaped identifier ends with a space.
+fn identifier(name: &str) -> String {
+    let plain = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain && !KEYWORDS.split_whitespace().any(|keyword| keyword == name) {
+        name.to_string()
+    } else {
+        format!("\\{name} ")
+    }
+}
+
+/// Returns the circuit as a structural Verilog module with one `and`, `or` or `xor` primitive per gate.
+pub fn to_verilog(circuit: &Circuit, module: &str) -> String {
+    let mut inputs: Vec<usize> = (0..circuit.len())
+        .filter(|&id| circuit.gate(id).is_none())
+        .collect();
+    let outputs: Vec<usize> = circuit.bus('z');
+    let mut wires: Vec<usize> = circuit
+        .gates()
+        .map(|(id, _)| id)
+        .filter(|id| !outputs.contains(id))
+        .collect();
+    inputs.sort_by_key(|&id| circuit.name(id));
+    wires.sort_by_key(|&id| circuit.name(id));
+
+    let mut ports: Vec<String> = inputs
+        .iter()
+        .map(|&id| format!("input wire {}", identifier(circuit.name(id))))
+        .collect();
+    ports.extend(
+        outputs
+            .iter()
+            .map(|&id| format!("output wire {}", identifier(circuit.name(id)))),
+    );
+
+    let mut verilog = format!(
+        "module {}(\n    {}\n);\n",
+        identifier(module),
+        ports.join(",\n    ")
+    );
+    for &id in &wires {
+        writeln!(verilog, "    wire {};", identifier(circuit.name(id))).unwrap();
+    }
+    verilog.push('\n');
+
+    // Gate instances share the namespace of the wires, so their prefix must not start any wire name
+    let mut prefix = String::from("g");
+    while (0..circuit.len()).any(|id| circuit.name(id).starts_with(&prefix)) {
+        prefix.insert(0, '_');
+    }
+
+    let mut gates: Vec<_> = circuit.gates().collect();
+    gates.sort_by_key(|&(id, _)| circuit.name(id));
+    for (i, (id, gate)) in gates.into_iter().enumerate() {
+        let primitive = match gate.op {
+            Op::And => "and",
+            Op::Or => "or",
+            Op::Xor => "xor",
+        };
+        let [left, right] = gate.inputs.map(|input| identifier(circuit.name(input)));
+        writeln!(
+            verilog,
+            "    {primitive} {prefix}{i}({}, {left}, {right});",
+            identifier(circuit.name(id))
+        )
+        .unwrap();
+    }
+
+    verilog.push_str("endmodule\n");
+    verilog
+}
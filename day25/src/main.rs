@@ -1,4 +1,4 @@
-use utils::test_solutions;
+use utils::{sections, test_solutions, Grid};
 
 fn main() {
     test_solutions(25, &first_part, Some(3), &second_part, None);
@@ -6,49 +6,26 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
+/// Splits the schematics into lock pins and key heights, each column counts its `#` cells.
 fn parse_input(input: &str) -> (Vec<[u32; 5]>, Vec<[u32; 5]>) {
     let mut lock_pins = vec![];
     let mut key_heights = vec![];
 
-    let mut current_grid = [0; 5];
-    let mut in_grid = false;
-    let mut top_grid = false;
+    for schematic in sections(input) {
+        let columns = Grid::parse(schematic).transpose();
 
-    let mut iter = input.lines();
-    loop {
-        let line_opt = iter.next();
-        if line_opt.is_none() || line_opt.unwrap() == "" {
-            if top_grid {
-                lock_pins.push(current_grid);
-            } else {
-                key_heights.push(current_grid);
-            }
-
-            in_grid = false;
-            current_grid = [0; 5];
-
-            if line_opt.is_none() {
-                break;
-            }
-            continue;
+        let mut heights = [0; 5];
+        for (height, column) in heights
+            .iter_mut()
+            .zip(columns.data.chunks(columns.width as usize))
+        {
+            *height = column.iter().filter(|&&c| c == b'#').count() as u32;
         }
 
-        let line = line_opt.unwrap();
-        if !in_grid {
-            if line == "#####" {
-                in_grid = true;
-                top_grid = true;
-            } else {
-                in_grid = true;
-                top_grid = false;
-            }
-        }
-
-        let chars = line.chars().collect::<Vec<char>>();
-        for i in 0..5 {
-            if chars[i] == '#' {
-                current_grid[i] += 1;
-            }
+        if schematic.starts_with("#####") {
+            lock_pins.push(heights);
+        } else {
+            key_heights.push(heights);
         }
     }
 
@@ -57,7 +34,6 @@ fn parse_input(input: &str) -> (Vec<[u32; 5]>, Vec<[u32; 5]>) {
 
 /* ------------------- Solutions ------------------- */
 
-#[allow(unused_variables)]
 fn first_part(input: &str) -> u32 {
     let (lock_pins, key_heights) = parse_input(input);
 
@@ -73,7 +49,6 @@ fn first_part(input: &str) -> u32 {
     unique_pairs
 }
 
-#[allow(unused_variables)]
-fn second_part(input: &str) -> i32 {
+fn second_part(_input: &str) -> i32 {
     0
 }
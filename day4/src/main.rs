@@ -1,5 +1,6 @@
-use glam::IVec2;
-use utils::test_solutions;
+use utils::{
+    test_solutions, Grid, Point, ALL_DIRECTIONS, BOTTOM_LEFT, BOTTOM_RIGHT, TOP_LEFT, TOP_RIGHT,
+};
 
 fn main() {
     test_solutions(4, &first_part, Some(18), &second_part, Some(9));
@@ -7,61 +8,27 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
-const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
-const DIRECTIONS: [[IVec2; 3]; 8] = [
-    [IVec2::new(0, 1), IVec2::new(0, 2), IVec2::new(0, 3)],
-    [IVec2::new(1, 0), IVec2::new(2, 0), IVec2::new(3, 0)],
-    [IVec2::new(0, -1), IVec2::new(0, -2), IVec2::new(0, -3)],
-    [IVec2::new(-1, 0), IVec2::new(-2, 0), IVec2::new(-3, 0)],
-    [IVec2::new(1, 1), IVec2::new(2, 2), IVec2::new(3, 3)],
-    [IVec2::new(-1, -1), IVec2::new(-2, -2), IVec2::new(-3, -3)],
-    [IVec2::new(1, -1), IVec2::new(2, -2), IVec2::new(3, -3)],
-    [IVec2::new(-1, 1), IVec2::new(-2, 2), IVec2::new(-3, 3)],
-];
-const CORNERS: [IVec2; 4] = [
-    IVec2::new(-1, -1),
-    IVec2::new(1, 1),
-    IVec2::new(1, -1),
-    IVec2::new(-1, 1),
-];
+const XMAS: [u8; 4] = [b'X', b'M', b'A', b'S'];
+const CORNERS: [Point; 4] = [TOP_LEFT, BOTTOM_RIGHT, TOP_RIGHT, BOTTOM_LEFT];
 
 /* ------------------- Solutions ------------------- */
 
 fn first_part(input: &str) -> i32 {
-    let grid: Vec<Vec<char>> = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
+    let grid = Grid::parse(input);
 
-    // grid[y][x]
     let mut xmas_count: i32 = 0;
-    for y in 0..grid.len() {
-        for x in 0..grid[y].len() {
-            let char = grid[y][x];
-            if char != 'X' {
+    for y in 0..grid.height as i32 {
+        for x in 0..grid.width as i32 {
+            let point = Point::new(x, y);
+            if grid[point] != b'X' {
                 continue;
             }
 
-            for directions in DIRECTIONS.iter() {
-                let mut possible: bool = true;
-                for i in 0..3 {
-                    let dir = directions[i];
-                    let new_x = x as i32 + dir.x;
-                    let new_y = y as i32 + dir.y;
-                    if new_y < 0
-                        || new_y >= grid.len() as i32
-                        || new_x < 0
-                        || new_x >= grid[new_y as usize].len() as i32
-                    {
-                        possible = false;
-                        break;
-                    }
-
-                    if grid[new_y as usize][new_x as usize] != XMAS[i + 1] {
-                        possible = false;
-                        break;
-                    }
-                }
+            for dir in ALL_DIRECTIONS {
+                let possible = (1..4).all(|i| {
+                    let next = point + dir * i;
+                    grid.contains(next) && grid[next] == XMAS[i as usize]
+                });
 
                 if possible {
                     xmas_count += 1;
@@ -74,30 +41,21 @@ fn first_part(input: &str) -> i32 {
 }
 
 fn second_part(input: &str) -> i32 {
-    let grid: Vec<Vec<char>> = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
+    let grid = Grid::parse(input);
 
     let mut xmas_count: i32 = 0;
-    for y in 1..grid.len() - 1 {
-        for x in 1..grid[y].len() - 1 {
-            let char = grid[y][x];
-            if char != 'A' {
+    for y in 1..grid.height as i32 - 1 {
+        for x in 1..grid.width as i32 - 1 {
+            let point = Point::new(x, y);
+            if grid[point] != b'A' {
                 continue;
             }
 
-            let mut corners: [char; 4] = ['.'; 4];
-            for i in 0..4 {
-                let dir = CORNERS[i];
-                let new_x = x as i32 + dir.x;
-                let new_y = y as i32 + dir.y;
-                corners[i] = grid[new_y as usize][new_x as usize];
-            }
-
-            if (corners[0] == 'M' && corners[1] == 'S' || corners[1] == 'M' && corners[0] == 'S')
-                && (corners[2] == 'M' && corners[3] == 'S'
-                    || corners[3] == 'M' && corners[2] == 'S')
+            let corners = CORNERS.map(|dir| grid[point + dir]);
+            if (corners[0] == b'M' && corners[1] == b'S'
+                || corners[1] == b'M' && corners[0] == b'S')
+                && (corners[2] == b'M' && corners[3] == b'S'
+                    || corners[3] == b'M' && corners[2] == b'S')
             {
                 xmas_count += 1;
             }
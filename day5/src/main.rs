@@ -1,4 +1,4 @@
-use utils::test_solutions;
+use utils::{parse, sections, test_solutions, List};
 
 fn main() {
     test_solutions(5, &first_part, Some(143), &second_part, Some(123));
@@ -8,25 +8,18 @@ fn main() {
 
 /// Parses the input and returns the rules and lines.
 fn parse_input(input: &str) -> (Vec<[i32; 2]>, Vec<Vec<i32>>) {
-    let data = input.split("\n\n").collect::<Vec<&str>>();
-
-    let rules = data[0]
-        .split("\n")
-        .map(|line| {
-            line.split("|")
-                .map(|s| s.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .map(|lines| [lines[0], lines[1]])
-        .collect::<Vec<[i32; 2]>>();
-    let lines = data[1]
-        .split("\n")
-        .map(|line| {
-            line.split(",")
-                .map(|s| s.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .collect::<Vec<Vec<i32>>>();
+    let mut sections = sections(input);
+    let (rules, lines) = (sections.next().unwrap(), sections.next().unwrap());
+
+    let rules = parse!("{}|{}")
+        .parse_lines_in(input, rules)
+        .unwrap_or_else(|error| panic!("{error}"));
+    let lines = parse!("{}")
+        .parse_lines_in(input, lines)
+        .unwrap_or_else(|error| panic!("{error}"))
+        .into_iter()
+        .map(|(List(line),)| line)
+        .collect();
 
     (rules, lines)
 }
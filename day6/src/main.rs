@@ -1,6 +1,5 @@
-use ahash::AHashSet;
 use rayon::prelude::*;
-use utils::test_solutions;
+use utils::{test_solutions, BitGrid, Grid, Point, DIRECTIONS};
 
 fn main() {
     test_solutions(6, &first_part, Some(41), &second_part, Some(6));
@@ -8,140 +7,85 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
-fn dir_to_vec(dir: u8) -> (i32, i32) {
-    [(0, -1), (1, 0), (0, 1), (-1, 0)][dir as usize]
-}
-
-fn parse_input(input: &str) -> (Vec<Vec<char>>, (usize, usize)) {
-    let mut grid: Vec<Vec<char>> = Vec::with_capacity(input.lines().count());
-    let mut guard: (usize, usize) = (0, 0);
-
-    for (y, line) in input.lines().enumerate() {
-        let mut subgrid: Vec<char> = vec![];
-        for (x, char) in line.chars().enumerate() {
-            subgrid.push(char);
-
-            if char == '^' {
-                guard = (x, y);
-            }
-        }
-        grid.push(subgrid);
-    }
+/// Index of `TOP` in `DIRECTIONS`, turning right is going to the next index.
+const START_DIRECTION: usize = 3;
 
+fn parse_input(input: &str) -> (Grid<u8>, Point) {
+    let grid = Grid::parse(input);
+    let guard = grid.find(b'^').unwrap();
     (grid, guard)
 }
 
-/* ------------------- Solutions ------------------- */
-
-#[allow(unused_variables)]
-fn first_part(input: &str) -> u32 {
-    let (mut grid, mut guard) = parse_input(input);
-    let height: usize = grid.len();
-    let width: usize = grid[0].len();
+/// Walks the guard until it leaves the map and returns every visited tile.
+fn walk(grid: &Grid<u8>, mut guard: Point) -> BitGrid {
+    let mut visited = BitGrid::new(grid.width, grid.height);
+    let mut direction = START_DIRECTION;
 
-    let mut total = 0;
-
-    let mut direction: (i32, i32) = (0, -1);
     loop {
-        let (fx, fy) = guard;
-
-        if grid[fy][fx] != 'x' {
-            total += 1;
-        }
-        grid[fy][fx] = 'x';
+        visited.insert(guard);
 
-        let (dx, dy) = direction;
-        let (nx, ny) = ((fx as i32 + dx) as usize, ((fy as i32) + dy) as usize);
-        if nx >= width || ny >= height {
-            break;
+        let next = guard + DIRECTIONS[direction];
+        if !grid.contains(next) {
+            return visited;
         }
 
-        match grid[ny][nx] {
-            '#' => {
-                direction = (-dy, dx);
-                guard = (fx, fy);
-            }
-            _ => {
-                guard = (nx, ny);
-            }
+        if grid[next] == b'#' {
+            direction = (direction + 1) % 4;
+        } else {
+            guard = next;
         }
     }
-
-    total
 }
 
-#[allow(unused_variables)]
-fn second_part(input: &str) -> u32 {
-    let (grid, main_guard) = parse_input(input);
-    let height: usize = grid.len();
-    let width: usize = grid[0].len();
-
-    // Run the guard once and store the path.
-    let mut guard = main_guard.clone();
+/// Walks the guard with an extra obstacle, it is looping if it ever faces the same way on the same tile twice.
+fn is_looping(grid: &Grid<u8>, mut guard: Point, obstacle: Point, seen: &mut [BitGrid; 4]) -> bool {
+    let mut direction = START_DIRECTION;
 
-    let mut main_path: AHashSet<(usize, usize)> = AHashSet::new();
-    let mut direction: u8 = 0;
     loop {
-        let (fx, fy) = guard;
-        let (dx, dy) = dir_to_vec(direction);
+        if !seen[direction].insert(guard) {
+            return true;
+        }
 
-        let (nx, ny) = ((fx as i32 + dx) as usize, ((fy as i32) + dy) as usize);
-        if nx >= width || ny >= height {
-            break;
+        let next = guard + DIRECTIONS[direction];
+        if !grid.contains(next) {
+            return false;
         }
 
-        match grid[ny][nx] {
-            '#' => {
-                direction = (direction + 1) % 4;
-                guard = (fx, fy);
-            }
-            '.' => {
-                guard = (nx, ny);
-                main_path.insert(guard.clone());
-            }
-            _ => {
-                guard = (nx, ny);
-            }
+        if next == obstacle || grid[next] == b'#' {
+            direction = (direction + 1) % 4;
+        } else {
+            guard = next;
         }
     }
+}
 
-    let looping_paths = main_path.par_iter().filter(|(x, y)| {
-        let mut guard = main_guard.clone();
-
-        let mut visited: Vec<Vec<i8>> = vec![vec![0; width]; height];
-        let mut direction: u8 = 0;
-
-        loop {
-            let (fx, fy) = guard;
-            let (dx, dy) = dir_to_vec(direction);
-
-            if visited[fy][fx] > 2 {
-                return true;
-            }
-
-            visited[fy][fx] += 1;
-
-            let (nx, ny) = ((fx as i32 + dx) as usize, ((fy as i32) + dy) as usize);
-            if nx >= width || ny >= height {
-                return false;
-            }
-
-            if ny == *y && nx == *x {
-                direction = (direction + 1) % 4;
-                guard = (fx, fy);
-            } else {
-                match grid[ny][nx] {
-                    '#' => {
-                        direction = (direction + 1) % 4;
-                        guard = (fx, fy);
-                    }
-                    _ => {
-                        guard = (nx, ny);
-                    }
-                }
-            }
-        }
-    });
+/* ------------------- Solutions ------------------- */
+
+fn first_part(input: &str) -> u32 {
+    let (grid, guard) = parse_input(input);
+    walk(&grid, guard).count_ones() as u32
+}
+
+fn second_part(input: &str) -> u32 {
+    let (grid, guard) = parse_input(input);
+
+    // Only the tiles on the original path can change where the guard goes.
+    let main_path: Vec<Point> = walk(&grid, guard)
+        .iter_ones()
+        .filter(|&p| p != guard)
+        .collect();
+
+    let empty = BitGrid::new(grid.width, grid.height);
+    let looping_paths = main_path
+        .par_iter()
+        .map_init(
+            || [empty.clone(), empty.clone(), empty.clone(), empty.clone()],
+            |seen, &obstacle| {
+                seen.iter_mut().for_each(BitGrid::clear);
+                is_looping(&grid, guard, obstacle, seen)
+            },
+        )
+        .filter(|&looping| looping);
 
     looping_paths.count() as u32
 }
@@ -1,4 +1,4 @@
-use utils::test_solutions;
+use utils::{test_solutions, Cursor};
 
 fn main() {
     test_solutions(7, &first_part, Some(3749), &second_part, Some(11387));
@@ -6,6 +6,18 @@ fn main() {
 
 /* ------------------- Helpers ------------------- */
 
+/// Parses the input into the answer and the numbers of every equation.
+fn parse_equations(input: &str) -> Vec<(i64, Vec<i64>)> {
+    let mut cursor = Cursor::from(input);
+    let mut equations = vec![];
+    while !cursor.is_empty() {
+        let mut numbers = cursor.line_unsigned().unwrap();
+        let answer = numbers.remove(0);
+        equations.push((answer, numbers));
+    }
+    equations
+}
+
 /* ------------------- Solutions ------------------- */
 
 #[allow(unused_variables)]
@@ -13,15 +25,7 @@ fn first_part(input: &str) -> i64 {
     let possible_operations = ['+', '*'];
 
     let mut result: i64 = 0;
-    for equation in input.lines() {
-        let data: Vec<&str> = equation.split(": ").collect::<Vec<&str>>();
-
-        let answer: i64 = data[0].parse::<i64>().unwrap();
-        let numbers: Vec<i64> = data[1]
-            .split(" ")
-            .map(|s| s.parse::<i64>().unwrap())
-            .collect::<Vec<i64>>();
-
+    for (answer, numbers) in parse_equations(input) {
         let mut num_checks: Vec<i64> = vec![];
         num_checks.push(answer);
 
@@ -54,15 +58,7 @@ fn second_part(input: &str) -> i64 {
     let possible_operations = ['+', '*'];
 
     let mut result: i64 = 0;
-    for equation in input.lines() {
-        let data: Vec<&str> = equation.split(": ").collect::<Vec<&str>>();
-
-        let answer: i64 = data[0].parse::<i64>().unwrap();
-        let numbers: Vec<i64> = data[1]
-            .split(" ")
-            .map(|s| s.parse::<i64>().unwrap())
-            .collect::<Vec<i64>>();
-
+    for (answer, numbers) in parse_equations(input) {
         let mut num_checks: Vec<i64> = vec![];
         num_checks.push(answer);
 
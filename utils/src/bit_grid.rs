@@ -0,0 +1,216 @@
+//! This module provides a `BitGrid` struct, a boolean grid packed into 64-bit words.
+//!
+//! It is meant for visited sets and masks, where `Grid<bool>` wastes a byte per cell.
+//! Counting is done with popcount and clearing or combining two grids works a whole word at a time.
+//! Cells are stored in the same row-major order as `Grid`, so the same points can be used for both.
+
+use crate::grid::Grid;
+use crate::point::Point;
+use std::ops::Index;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitGrid {
+    pub width: u32,
+    pub height: u32,
+    words: Vec<u64>,
+}
+
+/// Implements the constructors for `BitGrid`.
+/// `BitGrid::new` creates an empty grid, `BitGrid::from_grid` sets every cell of a `Grid` that matches the predicate.
+/// For example,
+/// ```
+/// use utils::{BitGrid, Grid, Point};
+///
+/// let walls = BitGrid::from_grid(&Grid::parse("#.\n.#"), |&c| c == b'#');
+/// assert_eq!(walls.count_ones(), 2);
+/// assert!(walls[Point::new(1, 1)]);
+/// assert!(!walls[Point::new(1, 0)]);
+/// ```
+impl BitGrid {
+    #[inline]
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        let cells = width as usize * height as usize;
+        BitGrid {
+            width,
+            height,
+            words: vec![0; cells.div_ceil(64)],
+        }
+    }
+
+    #[must_use]
+    pub fn from_grid<T>(grid: &Grid<T>, predicate: impl Fn(&T) -> bool) -> Self {
+        let mut result = BitGrid::new(grid.width, grid.height);
+        for (i, cell) in grid.data.iter().enumerate() {
+            if predicate(cell) {
+                result.words[i / 64] |= 1 << (i % 64);
+            }
+        }
+        result
+    }
+}
+
+/// Implements the single cell methods for `BitGrid`.
+/// `insert` sets a cell and returns whether it was unset before, just like `HashSet::insert`.
+/// `remove` unsets a cell and returns whether it was set before.
+/// Accessing a point outside the grid panics, use `contains` to check first.
+/// For example,
+/// ```
+/// use utils::{BitGrid, Point};
+///
+/// let mut visited = BitGrid::new(3, 3);
+/// assert!(visited.insert(Point::new(1, 2)));
+/// assert!(!visited.insert(Point::new(1, 2)));
+/// assert!(visited.get(Point::new(1, 2)));
+///
+/// visited.set(Point::new(0, 0), true);
+/// assert!(visited.remove(Point::new(1, 2)));
+/// assert!(!visited.contains(Point::new(3, 0)));
+/// assert_eq!(visited.iter_ones().collect::<Vec<_>>(), vec![Point::new(0, 0)]);
+/// ```
+impl BitGrid {
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0 && point.y >= 0 && point.x < self.width as i32 && point.y < self.height as i32
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get(&self, point: Point) -> bool {
+        let (word, bit) = self.locate(point);
+        self.words[word] & bit != 0
+    }
+
+    #[inline]
+    pub fn set(&mut self, point: Point, value: bool) {
+        let (word, bit) = self.locate(point);
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    #[inline]
+    pub fn insert(&mut self, point: Point) -> bool {
+        let (word, bit) = self.locate(point);
+        let was_unset = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        was_unset
+    }
+
+    #[inline]
+    pub fn remove(&mut self, point: Point) -> bool {
+        let (word, bit) = self.locate(point);
+        let was_set = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        was_set
+    }
+
+    /// Iterates over every set point in row-major order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = Point> + '_ {
+        let width = self.width as usize;
+        self.words.iter().enumerate().flat_map(move |(w, &word)| {
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let i = w * 64 + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(Point::new((i % width) as i32, (i / width) as i32))
+            })
+        })
+    }
+
+    #[inline]
+    fn locate(&self, point: Point) -> (usize, u64) {
+        if !self.contains(point) {
+            panic!(
+                "Point {point} is outside of the {}x{} bit grid",
+                self.width, self.height
+            );
+        }
+        let i = point.y as usize * self.width as usize + point.x as usize;
+        (i / 64, 1 << (i % 64))
+    }
+}
+
+/// Implements the whole grid methods for `BitGrid`.
+/// `count_ones` counts the set cells with popcount, `clear` unsets every cell.
+/// `union_with` and `intersect_with` combine two grids of the same size in place.
+/// For example,
+/// ```
+/// use utils::{BitGrid, Point};
+///
+/// let mut a = BitGrid::new(10, 10);
+/// let mut b = BitGrid::new(10, 10);
+/// a.insert(Point::new(1, 1));
+/// a.insert(Point::new(9, 9));
+/// b.insert(Point::new(9, 9));
+/// b.insert(Point::new(5, 0));
+///
+/// let mut union = a.clone();
+/// union.union_with(&b);
+/// assert_eq!(union.count_ones(), 3);
+///
+/// a.intersect_with(&b);
+/// assert_eq!(a.iter_ones().collect::<Vec<_>>(), vec![Point::new(9, 9)]);
+///
+/// a.clear();
+/// assert_eq!(a.count_ones(), 0);
+/// ```
+impl BitGrid {
+    #[inline]
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(a, b)| *a |= b);
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(a, b)| *a &= b);
+    }
+
+    fn assert_same_size(&self, other: &BitGrid) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "Bit grids must have the same size, {}x{} != {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+    }
+}
+
+/// Implements the `Index` trait for `BitGrid`, so `grid[point]` reads a cell just like in `Grid<bool>`.
+impl Index<Point> for BitGrid {
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        if self.get(index) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
@@ -0,0 +1,454 @@
+//! This module provides `RingDeque`, a ring-buffer deque, with its two storages `FixedSizeDeque` and `Deque`.
+//!
+//! Both push and pop at either end in O(1), which makes them the right queue for a BFS,
+//! where `Vec::remove(0)` or `FixedSizeVec::shift` would move every element on each pop.
+//! `FixedSizeDeque` keeps its elements inline with a capacity fixed at compile time, just like `FixedSizeVec`.
+//! `Deque` lives on the heap and doubles its capacity whenever it is full.
+//! The ring logic is shared, only creating and growing the buffer depends on the storage, see `RingStorage`.
+
+use std::fmt::{Debug, Formatter};
+use std::iter::Chain;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ops::{Index, IndexMut};
+use std::{ptr, slice};
+
+/// The buffer of a `RingDeque`, implemented for inline arrays and boxed slices.
+pub trait RingStorage<T>: AsRef<[MaybeUninit<T>]> + AsMut<[MaybeUninit<T>]> + Sized {
+    /// Returns an empty buffer with room for at least `capacity` values, if the storage can hold that many.
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Returns a larger empty buffer, or `None` if the storage cannot grow.
+    fn larger(&self) -> Option<Self>;
+}
+
+impl<T, const N: usize> RingStorage<T> for [MaybeUninit<T>; N] {
+    #[inline]
+    fn with_capacity(_: usize) -> Self {
+        [const { MaybeUninit::uninit() }; N]
+    }
+
+    #[inline]
+    fn larger(&self) -> Option<Self> {
+        None
+    }
+}
+
+impl<T> RingStorage<T> for Box<[MaybeUninit<T>]> {
+    #[inline]
+    fn with_capacity(capacity: usize) -> Self {
+        Box::new_uninit_slice(capacity)
+    }
+
+    #[inline]
+    fn larger(&self) -> Option<Self> {
+        Some(Box::new_uninit_slice((self.len() * 2).max(4)))
+    }
+}
+
+/// Maps a logical index to its slot, `index` must be smaller than `capacity`.
+#[inline]
+fn ring_index(head: usize, index: usize, capacity: usize) -> usize {
+    let slot = head + index;
+    if slot >= capacity {
+        slot - capacity
+    } else {
+        slot
+    }
+}
+
+pub type Iter<'a, T> = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+pub type IterMut<'a, T> = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+/// A deque with a fixed capacity, stored inline.
+pub type FixedSizeDeque<T, const N: usize> = RingDeque<T, [MaybeUninit<T>; N]>;
+
+/// A deque on the heap that grows when it is full.
+pub type Deque<T> = RingDeque<T, Box<[MaybeUninit<T>]>>;
+
+pub struct RingDeque<T, S: RingStorage<T>> {
+    head: usize,
+    length: usize,
+    data: S,
+    marker: PhantomData<T>,
+}
+
+impl<T, S: RingStorage<T>> Default for RingDeque<T, S> {
+    fn default() -> Self {
+        Self::from_storage(S::with_capacity(0))
+    }
+}
+
+/// Implements the constructor for `FixedSizeDeque`.
+impl<T, const N: usize> FixedSizeDeque<T, N> {
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            head: 0,
+            length: 0,
+            data: [const { MaybeUninit::uninit() }; N],
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Implements the constructors for `Deque`.
+impl<T> Deque<T> {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_storage(Box::new_uninit_slice(capacity))
+    }
+}
+
+/// Implements the push and pop methods for both deques.
+/// When a `FixedSizeDeque` is full, `push_back` and `push_front` panic and the `try_` versions hand the value back.
+/// A full `Deque` doubles its capacity instead, so pushing to it never fails.
+/// For example,
+/// ```
+/// use utils::{Deque, FixedSizeDeque};
+///
+/// let mut queue = FixedSizeDeque::<u32, 3>::new();
+/// queue.push_back(2);
+/// queue.push_back(3);
+/// queue.push_front(1);
+/// assert_eq!(queue.try_push_back(4), Err(4));
+///
+/// assert_eq!(queue.pop_front(), Some(1));
+/// queue.push_back(4);
+/// assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+///
+/// assert_eq!(queue.pop_back(), Some(4));
+/// assert_eq!((queue.front(), queue.back()), (Some(&2), Some(&3)));
+/// assert_eq!(queue[1], 3);
+///
+/// queue.clear();
+/// assert_eq!(queue.pop_front(), None);
+///
+/// let mut queue = Deque::with_capacity(2);
+/// for i in 0..10 {
+///     queue.push_back(i);
+/// }
+/// queue.push_front(-1);
+/// assert!(queue.capacity() >= 11);
+///
+/// assert_eq!(queue.pop_front(), Some(-1));
+/// assert_eq!(queue.pop_back(), Some(9));
+/// assert_eq!(queue.len(), 9);
+/// assert_eq!((queue.front(), queue.back()), (Some(&0), Some(&8)));
+/// assert_eq!(queue.iter().sum::<i32>(), 36);
+/// ```
+impl<T, S: RingStorage<T>> RingDeque<T, S> {
+    #[inline]
+    fn from_storage(data: S) -> Self {
+        Self {
+            head: 0,
+            length: 0,
+            data,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.as_ref().len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.length == self.capacity()
+    }
+
+    #[inline]
+    pub fn push_back(&mut self, value: T) {
+        if self.try_push_back(value).is_err() {
+            panic!("Cannot push more than {} elements", self.capacity());
+        }
+    }
+
+    #[inline]
+    pub fn push_front(&mut self, value: T) {
+        if self.try_push_front(value).is_err() {
+            panic!("Cannot push more than {} elements", self.capacity());
+        }
+    }
+
+    #[inline]
+    pub fn try_push_back(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() && !self.grow() {
+            return Err(value);
+        }
+        let slot = ring_index(self.head, self.length, self.capacity());
+        self.data.as_mut()[slot].write(value);
+        self.length += 1;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn try_push_front(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() && !self.grow() {
+            return Err(value);
+        }
+        self.head = ring_index(self.head, self.capacity() - 1, self.capacity());
+        self.data.as_mut()[self.head].write(value);
+        self.length += 1;
+        Ok(())
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let slot = self.head;
+        self.head = ring_index(self.head, 1, self.capacity());
+        self.length -= 1;
+        // SAFETY: the slot was initialized and is now outside of the ring, so it is read only once
+        Some(unsafe { self.data.as_ref()[slot].assume_init_read() })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.length -= 1;
+        let slot = ring_index(self.head, self.length, self.capacity());
+        // SAFETY: the slot was initialized and is now outside of the ring, so it is read only once
+        Some(unsafe { self.data.as_ref()[slot].assume_init_read() })
+    }
+
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back): (*mut [T], *mut [T]) = (front, back);
+        self.head = 0;
+        self.length = 0;
+        // SAFETY: both halves were initialized and are no longer reachable through `length`
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+
+    /// Moves the elements to the start of a larger buffer, returns false if the storage cannot grow.
+    fn grow(&mut self) -> bool {
+        let Some(mut data) = self.data.larger() else {
+            return false;
+        };
+        let (front, back) = self.as_slices();
+        // SAFETY: the old slots are never read again, `MaybeUninit` does not drop them with the old buffer
+        unsafe {
+            let target = data.as_mut().as_mut_ptr().cast::<T>();
+            ptr::copy_nonoverlapping(front.as_ptr(), target, front.len());
+            ptr::copy_nonoverlapping(back.as_ptr(), target.add(front.len()), back.len());
+        }
+        self.data = data;
+        self.head = 0;
+        true
+    }
+}
+
+/// Implements the accessors for both deques, indices count from the front.
+/// `as_slices` returns the elements as two slices, the second one is only non-empty when the ring wraps around.
+/// For example,
+/// ```
+/// use utils::{Deque, FixedSizeDeque};
+///
+/// let mut queue: FixedSizeDeque<char, 4> = "abcd".chars().collect();
+/// queue.pop_front();
+/// queue.push_back('e');
+/// assert_eq!(queue.as_slices(), (&['b', 'c', 'd'][..], &['e'][..]));
+///
+/// for c in &mut queue {
+///     *c = c.to_ascii_uppercase();
+/// }
+/// assert_eq!(queue.get(3), Some(&'E'));
+/// assert_eq!(queue.get(4), None);
+/// assert_eq!(format!("{:?}", queue.clone()), "['B', 'C', 'D', 'E']");
+///
+/// let mut queue: Deque<u8> = (1..=5).collect();
+/// queue[0] = 10;
+/// queue.iter_mut().for_each(|v| *v *= 2);
+/// assert_eq!(queue.get(0), Some(&20));
+/// assert_eq!(queue.get(5), None);
+/// assert_eq!(format!("{:?}", queue), "[20, 4, 6, 8, 10]");
+/// ```
+impl<T, S: RingStorage<T>> RingDeque<T, S> {
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.length {
+            return None;
+        }
+        let slot = ring_index(self.head, index, self.capacity());
+        // SAFETY: every index below `length` maps to an initialized slot
+        Some(unsafe { self.data.as_ref()[slot].assume_init_ref() })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.length {
+            return None;
+        }
+        let slot = ring_index(self.head, index, self.capacity());
+        // SAFETY: every index below `length` maps to an initialized slot
+        Some(unsafe { self.data.as_mut()[slot].assume_init_mut() })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.length.checked_sub(1)?)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let slots = self.data.as_ref();
+        let first = self.length.min(slots.len() - self.head);
+        let base = slots.as_ptr().cast::<T>();
+        // SAFETY: the ring holds `length` initialized values starting at `head`
+        unsafe {
+            (
+                slice::from_raw_parts(base.add(self.head), first),
+                slice::from_raw_parts(base, self.length - first),
+            )
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (head, length) = (self.head, self.length);
+        let slots = self.data.as_mut();
+        let first = length.min(slots.len() - head);
+        let base = slots.as_mut_ptr().cast::<T>();
+        // SAFETY: the ring holds `length` initialized values starting at `head`, the halves do not overlap
+        unsafe {
+            (
+                slice::from_raw_parts_mut(base.add(head), first),
+                slice::from_raw_parts_mut(base, length - first),
+            )
+        }
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back.iter())
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back.iter_mut())
+    }
+}
+
+impl<T, S: RingStorage<T>> Drop for RingDeque<T, S> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Clone, S: RingStorage<T>> Clone for RingDeque<T, S> {
+    fn clone(&self) -> Self {
+        let mut result = Self::from_storage(S::with_capacity(self.length));
+        result.extend(self.iter().cloned());
+        result
+    }
+}
+
+impl<T: Debug, S: RingStorage<T>> Debug for RingDeque<T, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S: RingStorage<T>> Index<usize> for RingDeque<T, S> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        let length = self.length;
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!("Index {index} is out of bounds for a deque of length {length}"),
+        }
+    }
+}
+
+impl<T, S: RingStorage<T>> IndexMut<usize> for RingDeque<T, S> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let length = self.length;
+        match self.get_mut(index) {
+            Some(value) => value,
+            None => panic!("Index {index} is out of bounds for a deque of length {length}"),
+        }
+    }
+}
+
+/// Implements `Extend` and `FromIterator` for both deques, the items are pushed to the back
+/// and have to fit into a `FixedSizeDeque`.
+impl<T, S: RingStorage<T>> Extend<T> for RingDeque<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push_back(value));
+    }
+}
+
+impl<T, S: RingStorage<T>> FromIterator<T> for RingDeque<T, S> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::default();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<'a, T, S: RingStorage<T>> IntoIterator for &'a RingDeque<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, S: RingStorage<T>> IntoIterator for &'a mut RingDeque<T, S> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -1,43 +1,82 @@
+//! This module provides a `FixedSizeVec` struct, a vector with inline storage and a capacity fixed at compile time.
+//!
+//! Elements live in a `[MaybeUninit<T>; N]` array, only the first `len` slots are initialized.
+//! It dereferences to a slice, so every slice method (`iter`, `get`, `sort`, indexing, ...) is available.
+//! `push` panics when the vector is full, `try_push` hands the value back instead.
+
 use std::fmt::{Debug, Formatter};
-use std::ops::{Index, IndexMut};
+use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut};
+use std::{ptr, slice};
 
-pub struct FixedSizeVec<T, const N: usize>
-where
-    T: Copy,
-{
+pub struct FixedSizeVec<T, const N: usize> {
     length: usize,
-    data: [Option<T>; N],
+    data: [MaybeUninit<T>; N],
 }
 
-impl<T, const N: usize> Default for FixedSizeVec<T, N>
-where
-    T: Copy,
-{
+impl<T, const N: usize> Default for FixedSizeVec<T, N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T, const N: usize> FixedSizeVec<T, N>
-where
-    T: Copy,
-{
+/// Implements the push and pop methods for `FixedSizeVec`.
+/// `shift` removes the first element by moving every other element, use `FixedSizeDeque` if that happens often.
+/// For example,
+/// ```
+/// use utils::FixedSizeVec;
+///
+/// let mut vec = FixedSizeVec::<u32, 3>::new();
+/// assert_eq!(vec.shift(), None);
+///
+/// vec.push(1);
+/// vec.push(2);
+/// assert_eq!(vec.try_push(3), Ok(()));
+/// assert_eq!(vec.try_push(4), Err(4));
+/// assert!(vec.is_full());
+///
+/// assert_eq!(vec.shift(), Some(1));
+/// assert_eq!(vec.pop(), Some(3));
+/// assert_eq!(vec.as_slice(), &[2]);
+/// assert_eq!(vec.capacity(), 3);
+/// ```
+impl<T, const N: usize> FixedSizeVec<T, N> {
     #[inline]
     #[must_use]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
             length: 0,
-            data: [None; N],
+            data: [const { MaybeUninit::uninit() }; N],
         }
     }
 
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.length == N
+    }
+
     #[inline]
     pub fn push(&mut self, value: T) {
-        if self.length == N {
+        if self.try_push(value).is_err() {
             panic!("Cannot push more than {} elements", N);
         }
-        self.data[self.length] = Some(value);
+    }
+
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.length == N {
+            return Err(value);
+        }
+        self.data[self.length].write(value);
         self.length += 1;
+        Ok(())
     }
 
     #[inline]
@@ -47,95 +86,266 @@ where
             return None;
         }
         self.length -= 1;
-        self.data[self.length]
+        // SAFETY: the slot was initialized and is now outside of `length`, so it is read only once
+        Some(unsafe { self.data[self.length].assume_init_read() })
     }
 
     #[inline]
     #[must_use]
     pub fn shift(&mut self) -> Option<T> {
-        let temp = self.data[0];
-        for i in 0..self.length - 1 {
-            self.data[i] = self.data[i + 1];
+        if self.length == 0 {
+            return None;
         }
-        self.data[self.length - 1] = None;
-        self.length -= 1;
-
-        temp
+        Some(self.remove(0))
     }
 
     #[inline]
     #[must_use]
-    pub fn get(&self, index: usize) -> Option<&T> {
-        if index >= self.length {
-            return None;
-        }
-        self.data[index].as_ref()
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `length` slots are initialized
+        unsafe { slice::from_raw_parts(self.data.as_ptr().cast(), self.length) }
     }
 
     #[inline]
     #[must_use]
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
-        if index >= self.length {
-            return None;
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `length` slots are initialized
+        unsafe { slice::from_raw_parts_mut(self.data.as_mut_ptr().cast(), self.length) }
+    }
+}
+
+/// Implements the `insert`, `remove`, `truncate` and `clear` methods for `FixedSizeVec`.
+/// `insert` panics if the index is past the end or the vector is full, `remove` panics if the index is out of bounds.
+/// For example,
+/// ```
+/// use utils::FixedSizeVec;
+///
+/// let mut vec: FixedSizeVec<char, 8> = "acd".chars().collect();
+/// vec.insert(1, 'b');
+/// vec.insert(4, 'e');
+/// assert_eq!(vec.as_slice(), &['a', 'b', 'c', 'd', 'e']);
+///
+/// assert_eq!(vec.remove(0), 'a');
+/// vec.truncate(2);
+/// assert_eq!(vec.as_slice(), &['b', 'c']);
+///
+/// vec.extend(['x', 'y']);
+/// assert_eq!(vec.iter().collect::<String>(), "bcxy");
+///
+/// vec.clear();
+/// assert!(vec.is_empty());
+/// ```
+impl<T, const N: usize> FixedSizeVec<T, N> {
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(
+            index <= self.length,
+            "Insert index {index} is out of bounds"
+        );
+        assert!(self.length < N, "Cannot insert more than {} elements", N);
+
+        // SAFETY: slots `index..length` are initialized and there is room for one more
+        unsafe {
+            let base = self.data.as_mut_ptr().cast::<T>();
+            ptr::copy(base.add(index), base.add(index + 1), self.length - index);
+            base.add(index).write(value);
         }
-        self.data[index].as_mut()
+        self.length += 1;
     }
 
-    #[inline]
-    #[must_use]
-    pub fn len(&self) -> usize {
-        self.length
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.length, "Remove index {index} is out of bounds");
+
+        // SAFETY: slot `index` is initialized, the hole is closed by moving the tail down
+        unsafe {
+            let base = self.data.as_mut_ptr().cast::<T>();
+            let value = base.add(index).read();
+            ptr::copy(
+                base.add(index + 1),
+                base.add(index),
+                self.length - index - 1,
+            );
+            self.length -= 1;
+            value
+        }
+    }
+
+    pub fn truncate(&mut self, length: usize) {
+        if length >= self.length {
+            return;
+        }
+
+        let tail = ptr::slice_from_raw_parts_mut(
+            self.data[length..].as_mut_ptr().cast::<T>(),
+            self.length - length,
+        );
+        self.length = length;
+        // SAFETY: the tail was initialized and is no longer reachable through `length`
+        unsafe { ptr::drop_in_place(tail) };
     }
 
     #[inline]
-    #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.length == 0
+    pub fn clear(&mut self) {
+        self.truncate(0);
     }
 }
 
-impl<T, const N: usize> FixedSizeVec<T, N>
-where
-    T: Copy,
-{
-    #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.data.iter().filter_map(|x| x.as_ref())
+impl<T, const N: usize> Drop for FixedSizeVec<T, N> {
+    fn drop(&mut self) {
+        self.clear();
     }
+}
+
+impl<T, const N: usize> Deref for FixedSizeVec<T, N> {
+    type Target = [T];
 
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
-        self.data.iter_mut().filter_map(|x| x.as_mut())
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
     }
 }
 
-impl<T, const N: usize> Index<usize> for FixedSizeVec<T, N>
-where
-    T: Copy,
-{
-    type Output = T;
+impl<T, const N: usize> DerefMut for FixedSizeVec<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
 
-    fn index(&self, index: usize) -> &Self::Output {
-        self.get(index).unwrap()
+impl<T: Clone, const N: usize> Clone for FixedSizeVec<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
     }
 }
 
-impl<T, const N: usize> IndexMut<usize> for FixedSizeVec<T, N>
-where
-    T: Copy,
-{
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        self.get_mut(index).unwrap()
+impl<T: PartialEq, const N: usize> PartialEq for FixedSizeVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
     }
 }
 
-impl<T, const N: usize> Debug for FixedSizeVec<T, N>
-where
-    T: Debug + Copy,
-{
+impl<T: Eq, const N: usize> Eq for FixedSizeVec<T, N> {}
+
+impl<T: Debug, const N: usize> Debug for FixedSizeVec<T, N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_list()
-            .entries(self.data.iter().filter(|x| x.is_some()))
-            .finish()
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Implements `Extend` and `FromIterator` for `FixedSizeVec`, both panic if the items do not fit.
+impl<T, const N: usize> Extend<T> for FixedSizeVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push(value));
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for FixedSizeVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+
+/// Implements `IntoIterator` for `FixedSizeVec`, by value and by reference.
+/// For example,
+/// ```
+/// use utils::FixedSizeVec;
+///
+/// let mut vec: FixedSizeVec<String, 4> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+/// for s in &mut vec {
+///     s.push('!');
+/// }
+///
+/// let mut iter = vec.clone().into_iter();
+/// assert_eq!(iter.next_back(), Some("c!".to_string()));
+/// assert_eq!(iter.len(), 2);
+/// assert_eq!(iter.collect::<Vec<_>>(), vec!["a!", "b!"]);
+/// assert_eq!((&vec).into_iter().count(), 3);
+/// ```
+impl<T, const N: usize> IntoIterator for FixedSizeVec<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut this = MaybeUninit::new(self);
+        // SAFETY: `this` is never dropped, so ownership of the elements moves to the iterator
+        let (length, data) = unsafe {
+            let this = this.as_mut_ptr();
+            ((*this).length, ptr::addr_of!((*this).data).read())
+        };
+        IntoIter {
+            start: 0,
+            end: length,
+            data,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a FixedSizeVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut FixedSizeVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct IntoIter<T, const N: usize> {
+    start: usize,
+    end: usize,
+    data: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        // SAFETY: slots `start..end` are initialized and each is read once
+        Some(unsafe { self.data[self.start - 1].assume_init_read() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: slots `start..end` are initialized and each is read once
+        Some(unsafe { self.data[self.end].assume_init_read() })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        let rest = ptr::slice_from_raw_parts_mut(
+            self.data[self.start..].as_mut_ptr().cast::<T>(),
+            self.end - self.start,
+        );
+        // SAFETY: the elements that were not yielded are still initialized
+        unsafe { ptr::drop_in_place(rest) };
     }
 }
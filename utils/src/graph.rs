@@ -0,0 +1,452 @@
+//! This module provides `Graph`, an undirected graph over nodes with any label, like the computers of day 23.
+//!
+//! Labels are interned into ids `0..len` in the order they are first seen, `id` and `label` convert between them.
+//! The neighbours of every node are kept as a `NodeSet`, a bitset over the ids, so checking an edge is a single bit
+//! and the common neighbours of two nodes are a word-wise AND.
+//! On top of that it finds cliques, every maximal one with Bron–Kerbosch, the largest ones and the number of k-cliques.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A set of node ids packed into 64-bit words, it grows when a larger id is inserted.
+#[derive(Clone, Debug, Default)]
+pub struct NodeSet {
+    words: Vec<u64>,
+}
+
+/// Implements the set methods for `NodeSet`.
+/// `insert` and `remove` return whether the set changed, just like `HashSet`.
+/// `intersection`, `union` and `difference` combine two sets a whole word at a time, `iter` yields the ids in increasing order.
+/// For example,
+/// ```
+/// use utils::NodeSet;
+///
+/// let mut a: NodeSet = [1, 5, 70].into_iter().collect();
+/// let b = NodeSet::full(6);
+/// assert!(a.insert(3));
+/// assert!(!a.insert(3));
+/// assert_eq!(a.len(), 4);
+///
+/// assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![1, 3, 5]);
+/// assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), vec![70]);
+/// assert_eq!(a.union(&b).len(), 7);
+/// assert!(a.remove(70));
+/// assert!(!a.contains(70));
+/// assert_eq!(a.first(), Some(1));
+/// ```
+impl NodeSet {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        NodeSet { words: vec![] }
+    }
+
+    /// Returns the set of every id in `0..len`.
+    #[must_use]
+    pub fn full(len: usize) -> Self {
+        let mut words = vec![u64::MAX; len / 64];
+        if !len.is_multiple_of(64) {
+            words.push((1 << (len % 64)) - 1);
+        }
+        NodeSet { words }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, id: usize) -> bool {
+        self.words
+            .get(id / 64)
+            .is_some_and(|word| word >> (id % 64) & 1 == 1)
+    }
+
+    #[inline]
+    pub fn insert(&mut self, id: usize) -> bool {
+        if id / 64 >= self.words.len() {
+            self.words.resize(id / 64 + 1, 0);
+        }
+        let word = &mut self.words[id / 64];
+        let added = *word >> (id % 64) & 1 == 0;
+        *word |= 1 << (id % 64);
+        added
+    }
+
+    #[inline]
+    pub fn remove(&mut self, id: usize) -> bool {
+        let Some(word) = self.words.get_mut(id / 64) else {
+            return false;
+        };
+        let removed = *word >> (id % 64) & 1 == 1;
+        *word &= !(1 << (id % 64));
+        removed
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Returns the smallest id in the set.
+    #[inline]
+    #[must_use]
+    pub fn first(&self) -> Option<usize> {
+        let (i, word) = self.words.iter().enumerate().find(|(_, &word)| word != 0)?;
+        Some(i * 64 + word.trailing_zeros() as usize)
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &NodeSet) -> NodeSet {
+        let words = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| a & b)
+            .collect();
+        NodeSet { words }
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &NodeSet) -> NodeSet {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = long.words.clone();
+        for (word, other) in words.iter_mut().zip(&short.words) {
+            *word |= other;
+        }
+        NodeSet { words }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &NodeSet) -> NodeSet {
+        let words = self
+            .words
+            .iter()
+            .enumerate()
+            .map(|(i, a)| a & !other.words.get(i).copied().unwrap_or_default())
+            .collect();
+        NodeSet { words }
+    }
+
+    /// Yields the ids in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+impl FromIterator<usize> for NodeSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = NodeSet::new();
+        for id in iter {
+            set.insert(id);
+        }
+        set
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Graph<L> {
+    labels: Vec<L>,
+    ids: HashMap<L, usize>,
+    adjacency: Vec<NodeSet>,
+}
+
+impl<L: Clone + Eq + Hash> Default for Graph<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implements the building and lookup methods for `Graph`.
+/// `add_node` interns a label and returns its id, `add_edge` interns both ends and connects them.
+/// `id` and `label` convert between the two, so `graph.id(graph.label(id)) == Some(id)` for every node.
+/// For example,
+/// ```
+/// use utils::Graph;
+///
+/// let graph: Graph<&str> = "kh-tc\nqp-kh\nde-cg\nka-co\nyn-aq\nqp-ub\ncg-tb\nkh-ub"
+///     .lines()
+///     .map(|line| line.split_once('-').unwrap())
+///     .collect();
+///
+/// assert_eq!(graph.len(), 11);
+/// let kh = graph.id("kh").unwrap();
+/// assert_eq!(graph.label(kh), &"kh");
+/// assert_eq!(graph.degree(kh), 3);
+/// assert!(graph.contains_edge(kh, graph.id("ub").unwrap()));
+/// assert!(!graph.contains_edge(kh, graph.id("de").unwrap()));
+///
+/// let mut neighbours: Vec<&str> = graph.neighbours(kh).iter().map(|id| *graph.label(id)).collect();
+/// neighbours.sort();
+/// assert_eq!(neighbours, vec!["qp", "tc", "ub"]);
+/// assert_eq!(graph.by_degree()[0], kh);
+/// ```
+impl<L: Clone + Eq + Hash> Graph<L> {
+    #[must_use]
+    pub fn new() -> Self {
+        Graph {
+            labels: vec![],
+            ids: HashMap::new(),
+            adjacency: vec![],
+        }
+    }
+
+    /// Returns the id of the node, adding it if the label is new.
+    pub fn add_node(&mut self, label: L) -> usize {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+
+        let id = self.labels.len();
+        self.labels.push(label.clone());
+        self.ids.insert(label, id);
+        self.adjacency.push(NodeSet::new());
+        id
+    }
+
+    /// Connects the two nodes, adding them if needed, and returns their ids.
+    pub fn add_edge(&mut self, a: L, b: L) -> (usize, usize) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.adjacency[a].insert(b);
+        self.adjacency[b].insert(a);
+        (a, b)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn id<Q>(&self, label: &Q) -> Option<usize>
+    where
+        L: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn label(&self, id: usize) -> &L {
+        &self.labels[id]
+    }
+
+    /// Returns the number of nodes.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn neighbours(&self, id: usize) -> &NodeSet {
+        &self.adjacency[id]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn degree(&self, id: usize) -> usize {
+        self.adjacency[id].len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains_edge(&self, a: usize, b: usize) -> bool {
+        self.adjacency[a].contains(b)
+    }
+
+    /// Returns every id ordered by decreasing degree, nodes with the same degree keep their id order.
+    #[must_use]
+    pub fn by_degree(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = (0..self.len()).collect();
+        ids.sort_by_key(|&id| std::cmp::Reverse(self.degree(id)));
+        ids
+    }
+}
+
+/// Implements the clique algorithms for `Graph`, every clique is a list of ids in increasing order.
+/// `maximal_cliques` is Bron–Kerbosch with pivoting, the pivot is the node that covers the most candidates.
+/// `maximum_cliques` runs the same search but skips branches that cannot reach the best size so far,
+/// it returns every clique of the largest size. `count_cliques` counts the cliques of exactly `k` nodes
+/// that contain at least one node matching the predicate.
+/// For example,
+/// ```
+/// use utils::Graph;
+///
+/// let graph: Graph<char> = [('a', 'b'), ('b', 'c'), ('c', 'd'), ('d', 'a'), ('a', 'c'), ('e', 'f')]
+///     .into_iter()
+///     .collect();
+/// let labels = |cliques: Vec<Vec<usize>>| -> Vec<String> {
+///     cliques.iter().map(|clique| clique.iter().map(|&id| graph.label(id)).collect()).collect()
+/// };
+///
+/// let mut maximal = labels(graph.maximal_cliques());
+/// maximal.sort();
+/// assert_eq!(maximal, vec!["abc", "acd", "ef"]);
+///
+/// let mut maximum = labels(graph.maximum_cliques());
+/// maximum.sort();
+/// assert_eq!(maximum, vec!["abc", "acd"]);
+///
+/// assert_eq!(graph.count_cliques(3, |_| true), 2);
+/// assert_eq!(graph.count_cliques(2, |id| *graph.label(id) == 'b'), 2);
+/// assert_eq!(graph.count_cliques(4, |_| true), 0);
+/// ```
+impl<L: Clone + Eq + Hash> Graph<L> {
+    #[must_use]
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let mut cliques = vec![];
+        self.bron_kerbosch(
+            &mut vec![],
+            NodeSet::full(self.len()),
+            NodeSet::new(),
+            &mut 0,
+            &mut |clique| {
+                cliques.push(clique.to_vec());
+                0
+            },
+        );
+        cliques
+    }
+
+    #[must_use]
+    pub fn maximum_cliques(&self) -> Vec<Vec<usize>> {
+        let mut cliques: Vec<Vec<usize>> = vec![];
+        self.bron_kerbosch(
+            &mut vec![],
+            NodeSet::full(self.len()),
+            NodeSet::new(),
+            &mut 0,
+            &mut |clique| {
+                if cliques
+                    .first()
+                    .is_some_and(|best| best.len() < clique.len())
+                {
+                    cliques.clear();
+                }
+                if cliques
+                    .first()
+                    .is_none_or(|best| best.len() == clique.len())
+                {
+                    cliques.push(clique.to_vec());
+                }
+                cliques[0].len()
+            },
+        );
+        cliques
+    }
+
+    #[must_use]
+    pub fn count_cliques(&self, k: usize, predicate: impl Fn(usize) -> bool) -> usize {
+        if k == 0 {
+            return 0;
+        }
+        let matches: NodeSet = (0..self.len()).filter(|&id| predicate(id)).collect();
+        self.count_from(k, &NodeSet::full(self.len()), false, &matches)
+    }
+
+    /// Recurses with the clique so far in `clique`, the nodes that could extend it in `candidates`
+    /// and the nodes that already had their turn in `excluded`. A clique is reported when both are empty,
+    /// `report` returns the size below which a branch is not worth exploring anymore.
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: NodeSet,
+        mut excluded: NodeSet,
+        bound: &mut usize,
+        report: &mut impl FnMut(&[usize]) -> usize,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                let mut sorted = clique.clone();
+                sorted.sort_unstable();
+                *bound = report(&sorted);
+            }
+            return;
+        }
+
+        let pivot = candidates
+            .union(&excluded)
+            .iter()
+            .max_by_key(|&u| candidates.intersection(&self.adjacency[u]).len())
+            .unwrap();
+
+        for node in candidates.difference(&self.adjacency[pivot]).iter() {
+            if clique.len() + candidates.len() < *bound {
+                return;
+            }
+
+            clique.push(node);
+            let next_candidates = candidates.intersection(&self.adjacency[node]);
+            let next_excluded = excluded.intersection(&self.adjacency[node]);
+            self.bron_kerbosch(clique, next_candidates, next_excluded, bound, report);
+            clique.pop();
+
+            candidates.remove(node);
+            excluded.insert(node);
+        }
+    }
+
+    /// Counts the cliques that extend the current one by `k` nodes from `candidates`.
+    /// Nodes are only added in increasing id order, so every clique is counted once.
+    fn count_from(
+        &self,
+        k: usize,
+        candidates: &NodeSet,
+        matched: bool,
+        matches: &NodeSet,
+    ) -> usize {
+        let mut count = 0;
+        for node in candidates.iter() {
+            let matched = matched || matches.contains(node);
+            if k == 1 {
+                count += matched as usize;
+                continue;
+            }
+
+            let mut next = candidates.intersection(&self.adjacency[node]);
+            // Only larger ids, the smaller ones already started their own cliques
+            for id in next.clone().iter().take_while(|&id| id <= node) {
+                next.remove(id);
+            }
+            count += self.count_from(k - 1, &next, matched, matches);
+        }
+        count
+    }
+}
+
+impl<L: Clone + Eq + Hash> FromIterator<(L, L)> for Graph<L> {
+    fn from_iter<I: IntoIterator<Item = (L, L)>>(iter: I) -> Self {
+        let mut graph = Graph::new();
+        for (a, b) in iter {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+}
@@ -5,7 +5,7 @@
 //! The `Grid` struct implements the `Index` and `IndexMut` traits, allowing you to access and modify values in the grid.
 //! Additional information about the `Grid` struct can be found in the module-level documentation.
 
-use crate::point::Point;
+use crate::point::{Point, ALL_DIRECTIONS, DIRECTIONS};
 use rayon::prelude::*;
 use std::ops::{Index, IndexMut};
 
@@ -61,6 +61,53 @@ impl<T: Copy> Grid<T> {
     }
 }
 
+/// Implements the `contains`, `index_of` and `point_of` methods for `Grid`.
+/// The `contains` method checks whether a point lies inside the bounds of the grid.
+/// The `index_of` method converts a point into an index of `data`, or `None` if the point is outside the grid.
+/// The `point_of` method converts an index of `data` back into a point, or `None` if the index is out of range.
+/// For example,
+/// ```
+/// use utils::{Grid, Point};
+///
+/// let grid = Grid::new(3, 2, 0);
+/// assert!(grid.contains(Point::new(2, 1)));
+/// assert!(!grid.contains(Point::new(3, 1)));
+/// assert!(!grid.contains(Point::new(0, -1)));
+///
+/// assert_eq!(grid.index_of(Point::new(2, 1)), Some(5));
+/// assert_eq!(grid.index_of(Point::new(-1, 1)), None);
+/// assert_eq!(grid.point_of(5), Some(Point::new(2, 1)));
+/// assert_eq!(grid.point_of(6), None);
+/// ```
+impl<T> Grid<T> {
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0 && point.y >= 0 && point.x < self.width as i32 && point.y < self.height as i32
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width as usize + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn point_of(&self, index: usize) -> Option<Point> {
+        if index < self.data.len() {
+            let width = self.width as usize;
+            Some(Point::new((index % width) as i32, (index / width) as i32))
+        } else {
+            None
+        }
+    }
+}
+
 impl Grid<u8> {
     #[inline]
     #[must_use]
@@ -68,7 +115,7 @@ impl Grid<u8> {
         let iter = input.par_lines().map(str::as_bytes).collect::<Vec<&[u8]>>();
 
         let height = iter.len() as u32;
-        let width = iter[0].len() as u32;
+        let width = iter.first().map_or(0, |line| line.len() as u32);
         let mut data = Vec::with_capacity((height * width) as usize);
         iter.iter().for_each(|&line| data.extend(line));
 
@@ -83,11 +130,12 @@ impl Grid<u8> {
 /// Implements the `Index` and `IndexMut` traits for `Grid`.
 /// The `Index` trait allows you to access values in the grid using points just like indices.
 /// The `IndexMut` trait allows you to modify values in the grid using points just like indices.
+/// Indexing with a point outside the grid panics instead of wrapping into another row.
 /// For example,
 /// ```
 /// use utils::{Grid, Point};
 ///
-/// let grid = Grid::new(3, 3, 0);
+/// let mut grid = Grid::new(3, 3, 0);
 /// assert_eq!(grid[Point::new(0, 0)], 0);
 /// assert_eq!(grid[Point::new(1, 1)], 0);
 ///
@@ -100,31 +148,147 @@ impl<T> Index<Point> for Grid<T> {
     type Output = T;
 
     #[inline]
-    #[must_use]
     fn index(&self, index: Point) -> &Self::Output {
-        &self.data[(index.y * self.width as i32 + index.x) as usize]
+        match self.index_of(index) {
+            Some(i) => &self.data[i],
+            None => panic!(
+                "Point {index} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
     }
 }
 
 impl<T> IndexMut<Point> for Grid<T> {
     #[inline]
-    #[must_use]
     fn index_mut(&mut self, index: Point) -> &mut Self::Output {
-        &mut self.data[(index.y * self.width as i32 + index.x) as usize]
+        match self.index_of(index) {
+            Some(i) => &mut self.data[i],
+            None => panic!(
+                "Point {index} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Implements the `positions` method for `Grid`.
+/// The `positions` method iterates over every point of the grid in row-major order.
+/// For example,
+/// ```
+/// use utils::{Grid, Point};
+///
+/// let grid = Grid::new(2, 2, 0);
+/// let points: Vec<Point> = grid.positions().collect();
+/// assert_eq!(points, vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]);
+/// ```
+impl<T> Grid<T> {
+    #[inline]
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i32;
+        let height = self.height as i32;
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+}
+
+/// Implements the `neighbours` and `all_neighbours` methods for `Grid`.
+/// `neighbours` yields the orthogonal neighbours of a point that are inside the grid, in `DIRECTIONS` order.
+/// `all_neighbours` also includes the diagonal ones, in `ALL_DIRECTIONS` order.
+/// For example,
+/// ```
+/// use utils::{Grid, Point};
+///
+/// let grid = Grid::new(3, 3, 0);
+/// let corner: Vec<Point> = grid.neighbours(Point::new(0, 0)).collect();
+/// assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+/// assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 4);
+/// assert_eq!(grid.all_neighbours(Point::new(1, 1)).count(), 8);
+/// assert_eq!(grid.all_neighbours(Point::new(2, 2)).count(), 3);
+/// ```
+impl<T> Grid<T> {
+    #[inline]
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS
+            .into_iter()
+            .map(move |dir| point + dir)
+            .filter(|&next| self.contains(next))
+    }
+
+    #[inline]
+    pub fn all_neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ALL_DIRECTIONS
+            .into_iter()
+            .map(move |dir| point + dir)
+            .filter(|&next| self.contains(next))
+    }
+}
+
+/// Implements the `row`, `rows`, `column` and `columns` methods for `Grid`.
+/// Rows are returned as slices, columns as iterators since they are not contiguous in memory.
+/// Asking for a row or column outside the grid panics.
+/// For example,
+/// ```
+/// use utils::Grid;
+///
+/// let grid = Grid::parse("ab\ncd\nef");
+/// assert_eq!(grid.row(1), b"cd");
+/// assert_eq!(grid.rows().collect::<Vec<_>>(), vec![b"ab", b"cd", b"ef"]);
+/// assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"bdf");
+///
+/// let columns: Vec<Vec<u8>> = grid.columns().map(|c| c.copied().collect()).collect();
+/// assert_eq!(columns, vec![b"ace".to_vec(), b"bdf".to_vec()]);
+///
+/// let empty = Grid::new(0, 3, 0u8);
+/// assert_eq!((empty.rows().count(), empty.columns().count()), (0, 0));
+/// assert_eq!(Grid::parse("").rows().count(), 0);
+/// ```
+impl<T> Grid<T> {
+    #[inline]
+    #[must_use]
+    pub fn row(&self, y: u32) -> &[T] {
+        assert!(y < self.height, "Row {y} is outside of the grid");
+        let start = (y * self.width) as usize;
+        &self.data[start..start + self.width as usize]
+    }
+
+    #[inline]
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // A grid without columns has no data, so any chunk size returns no rows, just like `columns`
+        self.data.chunks(self.width.max(1) as usize)
+    }
+
+    #[inline]
+    pub fn column(&self, x: u32) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {x} is outside of the grid");
+        self.data
+            .iter()
+            .skip(x as usize)
+            .step_by(self.width as usize)
+    }
+
+    #[inline]
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
     }
 }
 
-/// Implements the `find_all` and `find` methods for `Grid`.
-/// The `find_all` method returns a vector of all points in the grid that have the specified value.
+/// Implements the `find_all`, `find`, `find_by` and `count` methods for `Grid`.
+/// The `find_all` method returns a vector of all points in the grid that have the specified value, in row-major order.
 /// The `find` method returns the first point in the grid that has the specified value, or `None` if no points have the specified value.
+/// The `find_by` method returns the first point whose value matches the predicate, or `None` if there is no such point.
+/// Both `find` and `find_by` stop scanning at the first match.
+/// The `count` method returns the number of cells that have the specified value.
 /// For example,
 /// ```
 /// use utils::{Grid, Point};
 ///
 /// let grid = Grid::parse("....\n.S..\n....\n..S.");
-/// assert_eq!(grid.find_all(b'S'), vec![Point::new(1, 1), Point::new(3, 3)]);
+/// assert_eq!(grid.find_all(b'S'), vec![Point::new(1, 1), Point::new(2, 3)]);
 /// assert_eq!(grid.find(b'S'), Some(Point::new(1, 1)));
 /// assert_eq!(grid.find(b'E'), None);
+/// assert_eq!(grid.find_by(|&c| c != b'.'), Some(Point::new(1, 1)));
+/// assert_eq!(grid.count(b'S'), 2);
+/// assert_eq!(grid.count(b'.'), 14);
 /// ```
 impl<T: Copy + PartialEq + Sync> Grid<T> {
     #[inline]
@@ -133,20 +297,26 @@ impl<T: Copy + PartialEq + Sync> Grid<T> {
         self.data
             .par_iter()
             .enumerate()
-            .filter_map(|(i, &v)| {
-                if v == value {
-                    Some(Point::new(
-                        i as i32 % self.width as i32,
-                        i as i32 / self.width as i32,
-                    ))
-                } else {
-                    None
-                }
-            })
+            .filter_map(|(i, &v)| if v == value { self.point_of(i) } else { None })
             .collect()
     }
 
+    #[inline]
+    #[must_use]
     pub fn find(&self, value: T) -> Option<Point> {
-        self.find_all(value).pop()
+        self.find_by(|&v| v == value)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn find_by(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        let index = self.data.iter().position(predicate)?;
+        self.point_of(index)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn count(&self, value: T) -> usize {
+        self.data.iter().filter(|&&v| v == value).count()
     }
 }
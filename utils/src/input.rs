@@ -0,0 +1,83 @@
+//! This module provides the input clean-up shared by every loader in `runner`.
+//!
+//! `normalize` turns `\r\n` into `\n`, removes trailing whitespace from every line and drops the final newline,
+//! so an input saved on Windows or with extra blank lines at the end parses exactly like a clean one.
+//! `sections` splits an input into its blank line separated parts, like the rules and updates of day 5.
+
+/// Returns the input with `\n` line endings, no trailing whitespace on any line and no final newline.
+/// For example,
+/// ```
+/// use utils::normalize;
+///
+/// assert_eq!(normalize("a  \r\nb\r\n\r\nc\t\n\n\n"), "a\nb\n\nc");
+/// assert_eq!(normalize("  indented\n"), "  indented");
+/// assert_eq!(normalize("\r\n"), "");
+/// ```
+#[must_use]
+pub fn normalize(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    for line in input.lines() {
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+
+    result.truncate(result.trim_end().len());
+    result
+}
+
+/// Splits the input into sections separated by one or more blank lines, lines with only whitespace count as blank.
+/// Sections never include the newline that ends them, so they can be handed straight to `lines` or a `Pattern`.
+/// Sections are slices of the input, so lines inside a section keep their `\r\n` endings. `lines` still strips them,
+/// but text matched as a whole, like the blocks of `Pattern::parse_blocks`, needs a `normalize`d input first.
+/// Inputs loaded by the `runner` are always normalized.
+/// For example,
+/// ```
+/// use utils::{normalize, sections};
+///
+/// let input = "47|53\n97|13\n\n75,47,61\n\n\n97,61\n";
+/// assert_eq!(sections(input).collect::<Vec<_>>(), vec!["47|53\n97|13", "75,47,61", "97,61"]);
+///
+/// let windows = "x00: 1\r\n\r\nx00 AND y00 -> z00\r\n";
+/// assert_eq!(sections(windows).collect::<Vec<_>>(), vec!["x00: 1", "x00 AND y00 -> z00"]);
+///
+/// let windows = "a\r\nb\r\n\r\nc\r\n";
+/// assert_eq!(sections(windows).collect::<Vec<_>>(), vec!["a\r\nb", "c"]);
+/// assert_eq!(sections(windows).next().unwrap().lines().collect::<Vec<_>>(), vec!["a", "b"]);
+/// assert_eq!(sections(&normalize(windows)).collect::<Vec<_>>(), vec!["a\nb", "c"]);
+/// ```
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    std::iter::from_fn(move || {
+        // Skip the blank lines before the section
+        loop {
+            if rest.is_empty() {
+                return None;
+            }
+            let (line, tail) = split_line(rest);
+            if !line.trim().is_empty() {
+                break;
+            }
+            rest = tail;
+        }
+
+        let start = rest;
+        let mut end = 0;
+        while !rest.is_empty() {
+            let (line, tail) = split_line(rest);
+            if line.trim().is_empty() {
+                break;
+            }
+            end = start.len() - rest.len() + line.trim_end().len();
+            rest = tail;
+        }
+
+        Some(&start[..end])
+    })
+}
+
+fn split_line(input: &str) -> (&str, &str) {
+    match input.find('\n') {
+        Some(i) => (&input[..i], &input[i + 1..]),
+        None => (input, ""),
+    }
+}
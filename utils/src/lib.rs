@@ -1,11 +1,38 @@
+mod bit_grid;
+mod deque;
 mod fixed_size_vec;
+mod graph;
 mod grid;
+mod input;
+mod number;
+mod parse;
+mod pattern;
 mod point;
+mod point3;
+mod priority_queue;
+mod region;
+mod render;
 mod runner;
+mod sparse_grid;
 mod thread;
+mod torus;
+mod transform;
 
+pub use bit_grid::*;
+pub use deque::*;
 pub use fixed_size_vec::*;
+pub use graph::*;
 pub use grid::*;
+pub use input::*;
+pub use number::*;
+pub use parse::*;
+pub use pattern::*;
 pub use point::*;
+pub use point3::*;
+pub use priority_queue::*;
+pub use region::*;
+pub use render::*;
 pub use runner::*;
+pub use sparse_grid::*;
 pub use thread::*;
+pub use torus::*;
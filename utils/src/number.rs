@@ -0,0 +1,115 @@
+//! This module provides the `Number` and `Signed` traits used to make `Point`, `Point3` and the `parse` module generic.
+//! They are implemented for all primitive integer types through the `number!` macro.
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::*;
+
+pub trait Number:
+    Copy
+    + Debug
+    + Default
+    + Display
+    + Eq
+    + Hash
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TEN: Self;
+
+    /// Any number with at most this many decimal digits fits into the type, even when negative.
+    const SAFE_DIGITS: u32;
+
+    #[must_use]
+    fn abs_diff(self, other: Self) -> Self;
+
+    #[must_use]
+    fn rem_euclid(self, other: Self) -> Self;
+
+    /// Converts a single decimal digit (`0..=9`) into this type.
+    #[must_use]
+    fn from_digit(digit: u8) -> Self;
+
+    #[must_use]
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    #[must_use]
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    #[must_use]
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+pub trait Signed: Number + Neg<Output = Self> {
+    #[must_use]
+    fn abs(self) -> Self;
+
+    #[must_use]
+    fn signum(self) -> Self;
+}
+
+macro_rules! number {
+    ($($t:ty)*) => ($(
+        impl Number for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const TEN: Self = 10;
+            const SAFE_DIGITS: u32 = <$t>::MAX.ilog10();
+
+            #[inline]
+            fn abs_diff(self, other: Self) -> Self {
+                if self > other { self - other } else { other - self }
+            }
+
+            #[inline]
+            fn rem_euclid(self, other: Self) -> Self {
+                <$t>::rem_euclid(self, other)
+            }
+
+            #[inline]
+            fn from_digit(digit: u8) -> Self {
+                digit as $t
+            }
+
+            #[inline]
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            #[inline]
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            #[inline]
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*)
+}
+
+macro_rules! signed {
+    ($($t:ty)*) => ($(
+        impl Signed for $t {
+            #[inline]
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            #[inline]
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*)
+}
+
+number!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+signed!(i8 i16 i32 i64 i128 isize);
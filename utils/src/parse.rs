@@ -0,0 +1,341 @@
+//! This module provides a `Cursor` for parsing numbers straight out of the input bytes.
+//!
+//! Most inputs are numbers separated by some punctuation, so instead of `split` and `str::parse`
+//! the cursor skips to the next digit and reads the number in place, without allocating.
+//! Every error carries the byte offset where it happened, so a bad input is easy to find.
+//! Numbers are generic over `Number` and `Signed`, overflowing the target type is an error instead of wrapping.
+
+use crate::number::{Number, Signed};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended before a number was found.
+    UnexpectedEnd,
+    /// A byte that is not a decimal digit was found where one was required.
+    InvalidDigit(u8),
+    /// The number does not fit into the requested type.
+    Overflow,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ParseErrorKind::UnexpectedEnd => write!(f, "Unexpected end of input")?,
+            ParseErrorKind::InvalidDigit(byte) => {
+                write!(f, "Invalid digit {:?}", byte.escape_ascii().to_string())?
+            }
+            ParseErrorKind::Overflow => write!(f, "Number is too large")?,
+        }
+        write!(f, " at byte {}", self.offset)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> From<&'a str> for Cursor<'a> {
+    fn from(input: &'a str) -> Self {
+        Cursor::new(input.as_bytes())
+    }
+}
+
+/// Implements the movement methods for `Cursor`.
+/// `skip_until_digit` moves to the next ASCII digit and returns whether there was one,
+/// which makes `while cursor.skip_until_digit()` a loop over every number in the input.
+/// For example,
+/// ```
+/// use utils::Cursor;
+///
+/// let mut cursor = Cursor::from("ab 12\ncd");
+/// assert_eq!(cursor.peek(), Some(b'a'));
+/// assert!(cursor.skip_until_digit());
+/// assert_eq!(cursor.offset(), 3);
+///
+/// cursor.skip_line();
+/// assert_eq!(cursor.remaining(), b"cd");
+/// assert!(!cursor.skip_until_digit());
+/// assert!(cursor.is_empty());
+/// ```
+impl<'a> Cursor<'a> {
+    #[inline]
+    #[must_use]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Cursor { bytes, position: 0 }
+    }
+
+    /// Returns the byte offset of the cursor from the start of the input.
+    #[inline]
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.position
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.position.min(self.bytes.len())..]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    #[inline]
+    pub fn skip(&mut self, count: usize) {
+        self.position = (self.position + count).min(self.bytes.len());
+    }
+
+    #[inline]
+    pub fn skip_until_digit(&mut self) -> bool {
+        match self.remaining().iter().position(u8::is_ascii_digit) {
+            Some(i) => {
+                self.position += i;
+                true
+            }
+            None => {
+                self.position = self.bytes.len();
+                false
+            }
+        }
+    }
+
+    /// Moves past the next newline, or to the end if there is none.
+    #[inline]
+    pub fn skip_line(&mut self) {
+        match self.remaining().iter().position(|&b| b == b'\n') {
+            Some(i) => self.position += i + 1,
+            None => self.position = self.bytes.len(),
+        }
+    }
+
+    #[inline]
+    fn skip_while_digit(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.position += 1;
+        }
+    }
+
+    #[inline]
+    fn at_negative(&self) -> bool {
+        self.peek() == Some(b'-')
+            && self
+                .bytes
+                .get(self.position + 1)
+                .is_some_and(u8::is_ascii_digit)
+    }
+
+    #[inline]
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            offset: self.position,
+            kind,
+        }
+    }
+}
+
+/// Implements the number methods for `Cursor`.
+/// `next_unsigned` and `next_signed` skip everything up to the next number and read it, a `-` right before it makes it negative.
+/// `read_unsigned` and `read_signed` do not skip anything, the number has to start right under the cursor.
+/// `line_unsigned` and `line_signed` read every number up to the end of the line and move past it.
+/// For example,
+/// ```
+/// use utils::{Cursor, ParseErrorKind};
+///
+/// let mut cursor = Cursor::from("p=0,4 v=3,-3\n7: 1 -2 -\n300");
+/// assert_eq!(cursor.next_unsigned::<u8>(), Ok(0));
+/// assert_eq!(cursor.next_unsigned::<u8>(), Ok(4));
+/// assert_eq!(cursor.next_signed::<i32>(), Ok(3));
+/// assert_eq!(cursor.next_signed::<i32>(), Ok(-3));
+///
+/// cursor.skip_line();
+/// assert_eq!(cursor.line_signed::<i64>(), Ok(vec![7, 1, -2]));
+///
+/// let error = cursor.next_unsigned::<u8>().unwrap_err();
+/// assert_eq!(error.kind, ParseErrorKind::Overflow);
+/// assert_eq!(error.offset, 23);
+/// assert_eq!(error.to_string(), "Number is too large at byte 23");
+///
+/// assert_eq!(cursor.next_signed::<i8>().unwrap_err().kind, ParseErrorKind::UnexpectedEnd);
+/// assert_eq!(Cursor::from("-128").read_signed::<i8>(), Ok(i8::MIN));
+/// assert_eq!(Cursor::from("x1").read_unsigned::<u8>().unwrap_err().kind, ParseErrorKind::InvalidDigit(b'x'));
+/// ```
+impl Cursor<'_> {
+    pub fn next_unsigned<T: Number>(&mut self) -> Result<T, ParseError> {
+        if !self.skip_until_digit() {
+            return Err(self.error(ParseErrorKind::UnexpectedEnd));
+        }
+        self.digits(false)
+    }
+
+    pub fn next_signed<T: Signed>(&mut self) -> Result<T, ParseError> {
+        while let Some(byte) = self.peek() {
+            if byte.is_ascii_digit() {
+                return self.digits(false);
+            }
+            if self.at_negative() {
+                self.position += 1;
+                return self.digits(true);
+            }
+            self.position += 1;
+        }
+        Err(self.error(ParseErrorKind::UnexpectedEnd))
+    }
+
+    /// Reads the number right under the cursor, unlike `next_unsigned` nothing is skipped before it.
+    #[inline]
+    pub fn read_unsigned<T: Number>(&mut self) -> Result<T, ParseError> {
+        match self.peek() {
+            Some(b'0'..=b'9') => self.digits(false),
+            Some(byte) => Err(self.error(ParseErrorKind::InvalidDigit(byte))),
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    /// Reads the number right under the cursor, which may start with a `-`.
+    #[inline]
+    pub fn read_signed<T: Signed>(&mut self) -> Result<T, ParseError> {
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+            return match self.peek() {
+                Some(b'0'..=b'9') => self.digits(true),
+                Some(byte) => Err(self.error(ParseErrorKind::InvalidDigit(byte))),
+                None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+            };
+        }
+        self.read_unsigned()
+    }
+
+    pub fn line_unsigned<T: Number>(&mut self) -> Result<Vec<T>, ParseError> {
+        let mut result = vec![];
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'\n' => break,
+                b'0'..=b'9' => result.push(self.digits(false)?),
+                _ => self.position += 1,
+            }
+        }
+        self.skip(1);
+        Ok(result)
+    }
+
+    pub fn line_signed<T: Signed>(&mut self) -> Result<Vec<T>, ParseError> {
+        let mut result = vec![];
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'\n' => break,
+                b'0'..=b'9' => result.push(self.digits(false)?),
+                b'-' if self.at_negative() => {
+                    self.position += 1;
+                    result.push(self.digits(true)?);
+                }
+                _ => self.position += 1,
+            }
+        }
+        self.skip(1);
+        Ok(result)
+    }
+
+    /// Reads the digits under the cursor, negative numbers are accumulated downwards so `T::MIN` still fits.
+    /// The first `T::SAFE_DIGITS` digits can never overflow, only the ones after them are checked.
+    #[inline]
+    fn digits<T: Number>(&mut self, negative: bool) -> Result<T, ParseError> {
+        let rest = self.remaining();
+        let mut value = T::ZERO;
+        let mut count = 0;
+
+        while let Some(&byte) = rest.get(count) {
+            let digit = byte.wrapping_sub(b'0');
+            if digit > 9 {
+                break;
+            }
+
+            let digit = T::from_digit(digit);
+            let next = if count < T::SAFE_DIGITS as usize {
+                Some(if negative {
+                    value * T::TEN - digit
+                } else {
+                    value * T::TEN + digit
+                })
+            } else if negative {
+                value.checked_mul(T::TEN).and_then(|v| v.checked_sub(digit))
+            } else {
+                value.checked_mul(T::TEN).and_then(|v| v.checked_add(digit))
+            };
+
+            let Some(next) = next else {
+                // Move past the whole number, so the next call does not read the rest of it
+                let offset = self.position;
+                self.skip_while_digit();
+                return Err(ParseError {
+                    offset,
+                    kind: ParseErrorKind::Overflow,
+                });
+            };
+            value = next;
+            count += 1;
+        }
+
+        self.position += count;
+        Ok(value)
+    }
+}
+
+/// Implements the fixed-width parsing for `Cursor`.
+/// `next_fixed::<W>` reads exactly `W` digits without looking for separators, the loop has no early exit,
+/// so the compiler can unroll and vectorize it. `W` must be at most 19, so the result always fits into a `u64`.
+/// For example,
+/// ```
+/// use utils::{Cursor, ParseErrorKind};
+///
+/// let mut cursor = Cursor::from("0123456789 12a4");
+/// assert_eq!(cursor.next_fixed::<5>(), Ok(1234));
+/// assert_eq!(cursor.next_fixed::<5>(), Ok(56789));
+///
+/// cursor.skip(1);
+/// let error = cursor.next_fixed::<4>().unwrap_err();
+/// assert_eq!((error.kind, error.offset), (ParseErrorKind::InvalidDigit(b'a'), 13));
+/// assert_eq!(cursor.next_fixed::<8>().unwrap_err().kind, ParseErrorKind::UnexpectedEnd);
+/// ```
+impl Cursor<'_> {
+    pub fn next_fixed<const W: usize>(&mut self) -> Result<u64, ParseError> {
+        const { assert!(W <= 19, "At most 19 digits fit into a u64") };
+
+        let Some(chunk) = self.remaining().first_chunk::<W>() else {
+            return Err(self.error(ParseErrorKind::UnexpectedEnd));
+        };
+
+        let digits = chunk.map(|b| b.wrapping_sub(b'0'));
+        let invalid = digits.iter().fold(false, |invalid, &d| invalid | (d > 9));
+        if invalid {
+            let i = digits.iter().position(|&d| d > 9).unwrap();
+            return Err(ParseError {
+                offset: self.position + i,
+                kind: ParseErrorKind::InvalidDigit(chunk[i]),
+            });
+        }
+
+        self.position += W;
+        Ok(digits.iter().fold(0, |acc, &d| acc * 10 + d as u64))
+    }
+}
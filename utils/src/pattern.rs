@@ -0,0 +1,305 @@
+//! This module provides `Pattern`, a declarative way to pull typed fields out of lines like `Button A: X+94, Y+34`.
+//!
+//! A pattern is a format string where every `{}` is a field and everything else has to match literally.
+//! A field ends where the text after it in the pattern starts, the last field runs to the end of the line.
+//! Fields are converted with `FromStr` into a tuple or an array, see `FromFields`.
+//! Instead of panicking, errors report the line and column of the input where matching failed.
+
+use crate::input::sections;
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternErrorKind {
+    /// The literal text of the pattern was not found.
+    Expected(String),
+    /// A field could not be converted into the requested type.
+    InvalidField {
+        text: String,
+        type_name: &'static str,
+    },
+    /// The input continues after the end of the pattern.
+    TrailingText,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternError {
+    /// 1-based line of the input.
+    pub line: usize,
+    /// 1-based byte column inside the line.
+    pub column: usize,
+    pub kind: PatternErrorKind,
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            PatternErrorKind::Expected(literal) => write!(f, "expected {literal:?}"),
+            PatternErrorKind::InvalidField { text, type_name } => {
+                write!(f, "cannot parse {text:?} as {type_name}")
+            }
+            PatternErrorKind::TrailingText => write!(f, "unexpected text after the pattern"),
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+/// An error before it is located in the input, the offset is relative to the matched text.
+type RawError = (usize, PatternErrorKind);
+
+/// Converts the matched fields of a pattern, every field comes with its offset for error reporting.
+/// It is implemented for tuples of up to 8 elements and for arrays, every element has to implement `FromStr`.
+/// Patterns without fields convert into `()`.
+pub trait FromFields: Sized {
+    const COUNT: usize;
+
+    fn from_fields(fields: &[(usize, &str)]) -> Result<Self, RawError>;
+}
+
+fn field<T: FromStr>((offset, text): (usize, &str)) -> Result<T, RawError> {
+    text.parse().map_err(|_| {
+        let kind = PatternErrorKind::InvalidField {
+            text: text.to_string(),
+            type_name: type_name::<T>(),
+        };
+        (offset, kind)
+    })
+}
+
+macro_rules! tuple_fields {
+    ($count:literal: $($t:ident $i:tt)*) => {
+        impl<$($t: FromStr),*> FromFields for ($($t,)*) {
+            const COUNT: usize = $count;
+
+            fn from_fields(fields: &[(usize, &str)]) -> Result<Self, RawError> {
+                Ok(($(field::<$t>(fields[$i])?,)*))
+            }
+        }
+    };
+}
+
+impl FromFields for () {
+    const COUNT: usize = 0;
+
+    fn from_fields(_: &[(usize, &str)]) -> Result<Self, RawError> {
+        Ok(())
+    }
+}
+
+tuple_fields!(1: A 0);
+tuple_fields!(2: A 0 B 1);
+tuple_fields!(3: A 0 B 1 C 2);
+tuple_fields!(4: A 0 B 1 C 2 D 3);
+tuple_fields!(5: A 0 B 1 C 2 D 3 E 4);
+tuple_fields!(6: A 0 B 1 C 2 D 3 E 4 F 5);
+tuple_fields!(7: A 0 B 1 C 2 D 3 E 4 F 5 G 6);
+tuple_fields!(8: A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7);
+
+impl<T: FromStr, const N: usize> FromFields for [T; N] {
+    const COUNT: usize = N;
+
+    fn from_fields(fields: &[(usize, &str)]) -> Result<Self, RawError> {
+        let mut result = Vec::with_capacity(N);
+        for &f in fields {
+            result.push(field(f)?);
+        }
+        Ok(result.try_into().ok().unwrap())
+    }
+}
+
+/// A comma separated list field, like the `0,3,5,4` of `Program: 0,3,5,4`. Spaces around the items are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T: FromStr> FromStr for List<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|item| item.trim().parse())
+            .collect::<Result<_, _>>()
+            .map(List)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Pattern<'p> {
+    literals: Vec<&'p str>,
+}
+
+/// Implements the matching methods for `Pattern`, it is usually created with the `parse!` macro.
+/// `parse` matches a single piece of text, `parse_lines` matches every line and `parse_blocks` every block of
+/// lines separated by an empty line, for patterns that span multiple lines.
+/// `parse_lines_in` matches the lines of a section of the input and still reports lines of the whole input.
+/// A pattern without any `{}` only checks that the text matches it.
+/// For example,
+/// ```
+/// use utils::{parse, List, PatternErrorKind};
+///
+/// let (x, y): (i64, i64) = parse!("Button A: X+{}, Y+{}", "Button A: X+94, Y+34").unwrap();
+/// assert_eq!((x, y), (94, 34));
+///
+/// let rules = parse!("{}|{}").parse_lines::<[u32; 2]>("47|53\n97|13").unwrap();
+/// assert_eq!(rules, vec![[47, 53], [97, 13]]);
+///
+/// let (program,): (List<u8>,) = parse!("Program: {}", "Program: 0,3,5,4").unwrap();
+/// assert_eq!(program.0, vec![0, 3, 5, 4]);
+///
+/// let gate = parse!("{} {} {} -> {}");
+/// let (a, op, b, out): (String, String, String, String) = gate.parse("x00 AND y00 -> z00").unwrap();
+/// assert_eq!([a, op, b, out], ["x00", "AND", "y00", "z00"]);
+///
+/// let error = parse!("{}: {}").parse_lines::<(String, u8)>("x00: 1\nx01: 2\nx02 1").unwrap_err();
+/// assert_eq!((error.line, error.column), (3, 1));
+/// assert_eq!(error.kind, PatternErrorKind::Expected(": ".to_string()));
+///
+/// let error = parse!("Register A: {}").parse::<(u64,)>("Register A: 7a").unwrap_err();
+/// assert_eq!(error.to_string(), "Line 1, column 13: cannot parse \"7a\" as u64");
+///
+/// let input = "47|53\n\n75,47\n97;13";
+/// let updates = input.split("\n\n").nth(1).unwrap();
+/// let error = parse!("{},{}").parse_lines_in::<[u32; 2]>(input, updates).unwrap_err();
+/// assert_eq!((error.line, error.column), (4, 1));
+///
+/// let copy = updates.to_string();
+/// let error = parse!("{},{}").parse_lines_in::<[u32; 2]>(input, &copy).unwrap_err();
+/// assert_eq!((error.line, error.column), (2, 1));
+///
+/// assert_eq!(parse!("Program:").fields(), 0);
+/// assert_eq!(parse!("Program:", "Program:"), Ok(()));
+/// assert!(parse!("Program:").parse::<()>("Program: 0").is_err());
+/// ```
+impl<'p> Pattern<'p> {
+    #[must_use]
+    pub fn new(format: &'p str) -> Self {
+        let literals: Vec<&str> = format.split("{}").collect();
+        assert!(
+            literals.len() < 3
+                || literals[1..literals.len() - 1]
+                    .iter()
+                    .all(|l| !l.is_empty()),
+            "Fields of the pattern {format:?} must be separated by some text"
+        );
+        Pattern { literals }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn fields(&self) -> usize {
+        self.literals.len() - 1
+    }
+
+    pub fn parse<T: FromFields>(&self, text: &str) -> Result<T, PatternError> {
+        self.parse_in(text, text)
+    }
+
+    pub fn parse_lines<T: FromFields>(&self, input: &str) -> Result<Vec<T>, PatternError> {
+        self.parse_lines_in(input, input)
+    }
+
+    /// Matches every line of `section`, errors are located in the whole input if the section is a part of it
+    /// and in the section otherwise.
+    pub fn parse_lines_in<T: FromFields>(
+        &self,
+        input: &str,
+        section: &str,
+    ) -> Result<Vec<T>, PatternError> {
+        let input = if contains(input, section) {
+            input
+        } else {
+            section
+        };
+        section
+            .lines()
+            .map(|line| self.parse_in(input, line))
+            .collect()
+    }
+
+    pub fn parse_blocks<T: FromFields>(&self, input: &str) -> Result<Vec<T>, PatternError> {
+        sections(input)
+            .map(|block| self.parse_in(input, block))
+            .collect()
+    }
+
+    /// Matches `text`, errors are located in the whole input if the text is a part of it and in the text otherwise.
+    fn parse_in<T: FromFields>(&self, input: &str, text: &str) -> Result<T, PatternError> {
+        let input = if contains(input, text) { input } else { text };
+        assert_eq!(
+            T::COUNT,
+            self.fields(),
+            "The pattern has {} fields, but {} are requested",
+            self.fields(),
+            T::COUNT
+        );
+
+        self.split(text)
+            .and_then(|fields| T::from_fields(&fields))
+            .map_err(|(offset, kind)| {
+                let offset = text.as_ptr() as usize - input.as_ptr() as usize + offset;
+                let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+                PatternError {
+                    line: input[..offset].matches('\n').count() + 1,
+                    column: offset - line_start + 1,
+                    kind,
+                }
+            })
+    }
+
+    /// Splits the text into the offset and text of every field.
+    fn split<'a>(&self, text: &'a str) -> Result<Vec<(usize, &'a str)>, RawError> {
+        let expected = |offset: usize, literal: &str| {
+            (offset, PatternErrorKind::Expected(literal.to_string()))
+        };
+
+        let first = self.literals[0];
+        if !text.starts_with(first) {
+            return Err(expected(0, first));
+        }
+
+        let mut position = first.len();
+        let mut fields = Vec::with_capacity(self.fields());
+        for &literal in &self.literals[1..] {
+            let end = if literal.is_empty() {
+                text.len()
+            } else {
+                match text[position..].find(literal) {
+                    Some(i) => position + i,
+                    None => return Err(expected(position, literal)),
+                }
+            };
+
+            fields.push((position, &text[position..end]));
+            position = end + literal.len();
+        }
+
+        if position != text.len() {
+            return Err((position, PatternErrorKind::TrailingText));
+        }
+        Ok(fields)
+    }
+}
+
+/// Returns whether `inner` is a slice of `outer`, so its offset inside `outer` can be computed from the pointers.
+fn contains(outer: &str, inner: &str) -> bool {
+    let (outer, inner) = (
+        outer.as_bytes().as_ptr_range(),
+        inner.as_bytes().as_ptr_range(),
+    );
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Creates a `Pattern` from a format string, with a second argument it also parses that text right away.
+/// For example, `parse!("{}|{}", line)` is the same as `Pattern::new("{}|{}").parse(line)`.
+#[macro_export]
+macro_rules! parse {
+    ($format:literal) => {
+        $crate::Pattern::new($format)
+    };
+    ($format:literal, $text:expr) => {
+        $crate::Pattern::new($format).parse($text)
+    };
+}
@@ -2,25 +2,50 @@
 //!
 //! This module provides a `Point` struct and various constants and methods for working with points.
 //! The `Point` struct is a simple struct that represents a vector in two-dimensional space.
+//! `Point` is generic over any primitive integer type and defaults to `i32`.
 //! `Point` implements some arithmetic operations, allowing you to add, subtract, multiply, divide, etc.
 //! Additional information about the `Point` struct can be found in the module-level documentation.
 
+use crate::number::{Number, Signed};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use std::ops::*;
 
+pub const ORIGIN: Point = Point::new(0, 0);
+
 pub const RIGHT: Point = Point::new(1, 0);
 pub const BOTTOM: Point = Point::new(0, 1);
 pub const LEFT: Point = Point::new(-1, 0);
 pub const TOP: Point = Point::new(0, -1);
 pub const DIRECTIONS: [Point; 4] = [RIGHT, BOTTOM, LEFT, TOP];
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+pub const BOTTOM_RIGHT: Point = Point::new(1, 1);
+pub const BOTTOM_LEFT: Point = Point::new(-1, 1);
+pub const TOP_LEFT: Point = Point::new(-1, -1);
+pub const TOP_RIGHT: Point = Point::new(1, -1);
+pub const DIAGONALS: [Point; 4] = [BOTTOM_RIGHT, BOTTOM_LEFT, TOP_LEFT, TOP_RIGHT];
+
+/// All eight neighbours, clockwise starting from `RIGHT`.
+pub const ALL_DIRECTIONS: [Point; 8] = [
+    RIGHT,
+    BOTTOM_RIGHT,
+    BOTTOM,
+    BOTTOM_LEFT,
+    LEFT,
+    TOP_LEFT,
+    TOP,
+    TOP_RIGHT,
+];
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
 }
 
 /// Implements the `new` method for `Point`.
 /// This allows us to create a new `Point` object using the `Point::new` function.
+/// The coordinate type defaults to `i32`, but any primitive integer can be used.
 /// For example,
 /// ```
 /// use utils::Point;
@@ -28,8 +53,38 @@ pub struct Point {
 /// let point = Point::new(1, 2);
 /// assert_eq!(point.x, 1);
 /// assert_eq!(point.y, 2);
+///
+/// let big = Point::<i64>::new(10_000_000_000_000, 1);
+/// assert_eq!(big.x, 10_000_000_000_000);
 /// ```
 ///
+/// Implements the `try_cast` method for `Point`.
+/// This converts a `Point` into another coordinate type, returning `None` if any component does not fit.
+/// For example,
+/// ```
+/// use utils::Point;
+///
+/// assert_eq!(Point::new(3, 4).try_cast::<usize>(), Some(Point::new(3usize, 4)));
+/// assert_eq!(Point::new(-1, 4).try_cast::<usize>(), None);
+/// assert_eq!(Point::new(300u16, 4).try_cast::<u8>(), None);
+/// ```
+impl<T> Point<T> {
+    #[inline]
+    #[must_use]
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+        Some(Point::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+}
+
 /// Implements the `cw` and `ccw` methods for `Point`.
 /// This allows us to rotate a `Point` object 90 degrees clockwise or counter-clockwise.
 /// For example,
@@ -42,15 +97,9 @@ pub struct Point {
 ///
 /// let p3 = Point::new(0, -1);
 /// let p4 = p3.ccw();
-/// assert_eq!(p4, Point::new(1, 0));
+/// assert_eq!(p4, Point::new(-1, 0));
 /// ```
-impl Point {
-    #[inline]
-    #[must_use]
-    pub const fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
-    }
-
+impl<T: Signed> Point<T> {
     #[inline]
     #[must_use]
     pub fn cw(self) -> Self {
@@ -64,6 +113,111 @@ impl Point {
     }
 }
 
+/// Implements the distance and component-wise helper methods for `Point`.
+/// `manhattan` and `chebyshev` measure the distance between two points in taxicab and king-move metrics.
+/// `rem_euclid`, `abs` and `signum` are applied to both components separately, the last two only for signed types.
+/// `rem_euclid` always returns non-negative components, which makes it suitable for wrap-around grids.
+/// For example,
+/// ```
+/// use utils::Point;
+///
+/// let p1 = Point::new(1, -2);
+/// let p2 = Point::new(-3, 4);
+/// assert_eq!(p1.manhattan(p2), 10);
+/// assert_eq!(p1.chebyshev(p2), 6);
+/// assert_eq!(p2.abs(), Point::new(3, 4));
+/// assert_eq!(p2.signum(), Point::new(-1, 1));
+///
+/// let robot = Point::new(-4, 105);
+/// assert_eq!(robot.rem_euclid(Point::new(101, 103)), Point::new(97, 2));
+/// ```
+impl<T: Number> Point<T> {
+    #[inline]
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn rem_euclid(self, other: Self) -> Self {
+        Point::new(self.x.rem_euclid(other.x), self.y.rem_euclid(other.y))
+    }
+}
+
+impl<T: Signed> Point<T> {
+    #[inline]
+    #[must_use]
+    pub fn abs(self) -> Self {
+        Point::new(self.x.abs(), self.y.abs())
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn signum(self) -> Self {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+}
+
+/// Implements `Ord` for `Point` in row-major (reading) order, `y` first and then `x`.
+/// For example,
+/// ```
+/// use utils::Point;
+///
+/// let mut points = vec![Point::new(2, 1), Point::new(0, 2), Point::new(1, 1)];
+/// points.sort();
+/// assert_eq!(points, vec![Point::new(1, 1), Point::new(2, 1), Point::new(0, 2)]);
+/// ```
+impl<T: Number> Ord for Point<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl<T: Number> PartialOrd for Point<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Implements `Display` for `Point` as `(x, y)`.
+/// For example,
+/// ```
+/// use utils::Point;
+///
+/// assert_eq!(Point::new(3, -4).to_string(), "(3, -4)");
+/// ```
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Implements `Neg` for `Point`, flipping the direction of the vector.
+/// For example,
+/// ```
+/// use utils::{Point, LEFT, RIGHT};
+///
+/// assert_eq!(-RIGHT, LEFT);
+/// assert_eq!(-Point::new(1, -2), Point::new(-1, 2));
+/// ```
+impl<T: Signed> Neg for Point<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
 /// Implements point-based basic arithmetic operations for `Point`.
 ///
 /// # Examples
@@ -79,32 +233,50 @@ impl Point {
 /// let mut p4 = Point::new(5, 6);
 /// let p5 = p4 % p2;
 /// assert_eq!(p5, Point::new(2, 2));
+///
+/// assert_eq!(p1 * p2, Point::new(3, 8));
+/// assert_eq!(p2 / p1, Point::new(3, 2));
 /// ```
-impl Add for Point {
+impl<T: Number> Add for Point<T> {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn add(self, other: Self) -> Self::Output {
         Point::new(self.x + other.x, self.y + other.y)
     }
 }
 
-impl Sub for Point {
+impl<T: Number> Sub for Point<T> {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn sub(self, other: Self) -> Self::Output {
         Point::new(self.x - other.x, self.y - other.y)
     }
 }
 
-impl Rem for Point {
+impl<T: Number> Mul for Point<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self::Output {
+        Point::new(self.x * other.x, self.y * other.y)
+    }
+}
+
+impl<T: Number> Div for Point<T> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, other: Self) -> Self::Output {
+        Point::new(self.x / other.x, self.y / other.y)
+    }
+}
+
+impl<T: Number> Rem for Point<T> {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn rem(self, other: Self) -> Self::Output {
         Point::new(self.x % other.x, self.y % other.y)
     }
@@ -126,21 +298,35 @@ impl Rem for Point {
 /// p3 %= p2;
 /// assert_eq!(p3, Point::new(2, 2));
 /// ```
-impl AddAssign for Point {
+impl<T: Number> AddAssign for Point<T> {
     #[inline]
     fn add_assign(&mut self, other: Self) {
         *self = *self + other;
     }
 }
 
-impl SubAssign for Point {
+impl<T: Number> SubAssign for Point<T> {
     #[inline]
     fn sub_assign(&mut self, other: Self) {
         *self = *self - other;
     }
 }
 
-impl RemAssign for Point {
+impl<T: Number> MulAssign for Point<T> {
+    #[inline]
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Number> DivAssign for Point<T> {
+    #[inline]
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<T: Number> RemAssign for Point<T> {
     #[inline]
     fn rem_assign(&mut self, other: Self) {
         *self = *self % other;
@@ -162,68 +348,48 @@ impl RemAssign for Point {
 /// let p4 = p3 / 2;
 /// assert_eq!(p4, Point::new(4, 3));
 /// ```
-impl<T> Add<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> Add<T> for Point<T> {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn add(self, other: T) -> Self::Output {
-        Point::new(self.x + other.into(), self.y + other.into())
+        Point::new(self.x + other, self.y + other)
     }
 }
 
-impl<T> Sub<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> Sub<T> for Point<T> {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn sub(self, other: T) -> Self::Output {
-        Point::new(self.x - other.into(), self.y - other.into())
+        Point::new(self.x - other, self.y - other)
     }
 }
 
-impl<T> Mul<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> Mul<T> for Point<T> {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn mul(self, other: T) -> Self::Output {
-        Point::new(self.x * other.into(), self.y * other.into())
+        Point::new(self.x * other, self.y * other)
     }
 }
 
-impl<T> Div<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> Div<T> for Point<T> {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn div(self, other: T) -> Self::Output {
-        Point::new(self.x / other.into(), self.y / other.into())
+        Point::new(self.x / other, self.y / other)
     }
 }
 
-impl<T> Rem<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> Rem<T> for Point<T> {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn rem(self, other: T) -> Self::Output {
-        Point::new(self.x % other.into(), self.y % other.into())
+        Point::new(self.x % other, self.y % other)
     }
 }
 
@@ -242,40 +408,35 @@ where
 /// p2 /= 2;
 /// assert_eq!(p2, Point::new(4, 3));
 /// ```
-impl<T> AddAssign<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> AddAssign<T> for Point<T> {
     #[inline]
     fn add_assign(&mut self, other: T) {
         *self = *self + other;
     }
 }
 
-impl<T> SubAssign<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> SubAssign<T> for Point<T> {
     #[inline]
     fn sub_assign(&mut self, other: T) {
         *self = *self - other;
     }
 }
 
-impl<T> DivAssign<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> MulAssign<T> for Point<T> {
+    #[inline]
+    fn mul_assign(&mut self, other: T) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Number> DivAssign<T> for Point<T> {
     #[inline]
     fn div_assign(&mut self, other: T) {
         *self = *self / other;
     }
 }
 
-impl<T> RemAssign<T> for Point
-where
-    T: Copy + Into<i32>,
-{
+impl<T: Number> RemAssign<T> for Point<T> {
     #[inline]
     fn rem_assign(&mut self, other: T) {
         *self = *self % other;
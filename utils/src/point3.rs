@@ -0,0 +1,123 @@
+//! This module provides a `Point3` struct, the three-dimensional sibling of `Point`.
+//! `Point3` is generic over any primitive integer type and defaults to `i32`.
+//! It implements the same basic arithmetic as `Point`, plus the distance helpers.
+
+use crate::number::{Number, Signed};
+use std::fmt::{Display, Formatter};
+use std::ops::*;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Point3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Implements the `new`, `manhattan` and `chebyshev` methods for `Point3`.
+/// For example,
+/// ```
+/// use utils::Point3;
+///
+/// let p1 = Point3::new(1, 2, 3);
+/// let p2 = Point3::new(-1, 4, 0);
+/// assert_eq!(p1.manhattan(p2), 7);
+/// assert_eq!(p1.chebyshev(p2), 3);
+/// assert_eq!(p1.to_string(), "(1, 2, 3)");
+/// ```
+impl<T> Point3<T> {
+    #[inline]
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Number> Point3<T> {
+    #[inline]
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Implements basic arithmetic operations for `Point3`.
+///
+/// # Examples
+///
+/// ```
+/// use utils::Point3;
+///
+/// let mut p1 = Point3::new(1, 2, 3);
+/// let p2 = Point3::new(3, 4, 5);
+/// assert_eq!(p1 + p2, Point3::new(4, 6, 8));
+/// assert_eq!(p2 - p1, Point3::new(2, 2, 2));
+/// assert_eq!(p1 * 2, Point3::new(2, 4, 6));
+/// assert_eq!(-p1, Point3::new(-1, -2, -3));
+///
+/// p1 += p2;
+/// assert_eq!(p1, Point3::new(4, 6, 8));
+/// ```
+impl<T: Signed> Neg for Point3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Number> Add for Point3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self::Output {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Number> Sub for Point3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self::Output {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Number> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: T) -> Self::Output {
+        Point3::new(self.x * other, self.y * other, self.z * other)
+    }
+}
+
+impl<T: Number> AddAssign for Point3<T> {
+    #[inline]
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Number> SubAssign for Point3<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
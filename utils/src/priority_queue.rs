@@ -0,0 +1,185 @@
+//! This module provides two monotone priority queues for Dijkstra with small integer costs.
+//!
+//! Both only work if every pushed priority is at least the last popped one, which always holds in Dijkstra.
+//! `BucketQueue` is Dial's algorithm, a ring of `max_weight + 1` buckets, for when every edge costs at most `max_weight`.
+//! `RadixHeap` has no limit on the edge weights and keeps its items in 33 buckets based on their highest differing bit.
+//! Popping from either is amortized O(1) (`BucketQueue`) or O(log C) (`RadixHeap`), much cheaper than a `BinaryHeap`.
+
+#[derive(Clone, Debug)]
+pub struct BucketQueue<T> {
+    buckets: Vec<Vec<T>>,
+    current: u32,
+    length: usize,
+}
+
+/// Implements Dial's algorithm for `BucketQueue`.
+/// Every pushed priority must be between the last popped priority and that plus `max_weight`, otherwise it panics.
+/// Items with the same priority are popped in an unspecified order.
+/// For example, Dijkstra over a grid where entering a cell costs its digit,
+/// ```
+/// use utils::{BucketQueue, Grid, Point};
+///
+/// let grid = Grid::parse("131\n191\n111");
+/// let mut costs = grid.clone_with(u32::MAX);
+/// let mut queue = BucketQueue::new(9);
+///
+/// costs[Point::new(0, 0)] = 0;
+/// queue.push(0, Point::new(0, 0));
+/// while let Some((cost, point)) = queue.pop() {
+///     if cost > costs[point] {
+///         continue;
+///     }
+///     for next in grid.neighbours(point) {
+///         let next_cost = cost + (grid[next] - b'0') as u32;
+///         if next_cost < costs[next] {
+///             costs[next] = next_cost;
+///             queue.push(next_cost, next);
+///         }
+///     }
+/// }
+/// assert_eq!(costs[Point::new(2, 2)], 4);
+/// assert_eq!(costs[Point::new(1, 1)], 10);
+/// ```
+impl<T> BucketQueue<T> {
+    #[must_use]
+    pub fn new(max_weight: u32) -> Self {
+        BucketQueue {
+            buckets: (0..=max_weight).map(|_| Vec::new()).collect(),
+            current: 0,
+            length: 0,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    #[inline]
+    pub fn push(&mut self, priority: u32, item: T) {
+        let window = self.buckets.len() as u32;
+        assert!(
+            priority >= self.current && priority - self.current < window,
+            "Priority {priority} is outside of the bucket window {}..{}",
+            self.current,
+            self.current as u64 + window as u64
+        );
+        self.buckets[(priority % window) as usize].push(item);
+        self.length += 1;
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<(u32, T)> {
+        if self.length == 0 {
+            return None;
+        }
+
+        let window = self.buckets.len() as u32;
+        loop {
+            if let Some(item) = self.buckets[(self.current % window) as usize].pop() {
+                self.length -= 1;
+                return Some((self.current, item));
+            }
+            self.current += 1;
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RadixHeap<T> {
+    buckets: [Vec<(u32, T)>; 33],
+    last: u32,
+    length: usize,
+}
+
+impl<T> Default for RadixHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implements the radix heap for `RadixHeap`.
+/// Pushing a priority below the last popped one panics, there is no upper limit.
+/// Bucket `i` holds the items whose priority first differs from the last popped one at bit `i - 1`.
+/// Popping from an empty first bucket redistributes the next non-empty bucket around its minimum.
+/// For example,
+/// ```
+/// use utils::RadixHeap;
+///
+/// let mut heap = RadixHeap::new();
+/// heap.push(1_000_000, 'c');
+/// heap.push(5, 'a');
+/// heap.push(70, 'b');
+/// assert_eq!(heap.pop(), Some((5, 'a')));
+///
+/// heap.push(5, 'd');
+/// heap.push(70_000, 'e');
+/// assert_eq!(heap.len(), 4);
+///
+/// let order: String = std::iter::from_fn(|| heap.pop()).map(|(_, c)| c).collect();
+/// assert_eq!(order, "dbec");
+/// assert!(heap.is_empty());
+/// ```
+impl<T> RadixHeap<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        RadixHeap {
+            buckets: std::array::from_fn(|_| Vec::new()),
+            last: 0,
+            length: 0,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    #[inline]
+    pub fn push(&mut self, priority: u32, item: T) {
+        assert!(
+            priority >= self.last,
+            "Priority {priority} is lower than the last popped priority {}",
+            self.last
+        );
+        self.buckets[Self::bucket(priority, self.last)].push((priority, item));
+        self.length += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<(u32, T)> {
+        if self.length == 0 {
+            return None;
+        }
+
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|b| !b.is_empty()).unwrap();
+            let items = std::mem::take(&mut self.buckets[i]);
+            self.last = items.iter().map(|&(priority, _)| priority).min().unwrap();
+            for (priority, item) in items {
+                self.buckets[Self::bucket(priority, self.last)].push((priority, item));
+            }
+        }
+
+        self.length -= 1;
+        self.buckets[0].pop()
+    }
+
+    #[inline]
+    fn bucket(priority: u32, last: u32) -> usize {
+        (u32::BITS - (priority ^ last).leading_zeros()) as usize
+    }
+}
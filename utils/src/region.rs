@@ -0,0 +1,191 @@
+//! This module provides region analysis on top of `Grid`.
+//!
+//! A region is a maximal set of orthogonally connected cells that share the same value.
+//! For every region we compute its area, perimeter, number of straight sides, bounding box and holes,
+//! all in linear time over the size of the grid.
+
+use crate::bit_grid::BitGrid;
+use crate::grid::Grid;
+use crate::point::{Point, DIRECTIONS};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub cells: Vec<Point>,
+    pub perimeter: u32,
+    pub sides: u32,
+    pub holes: u32,
+    pub min: Point,
+    pub max: Point,
+}
+
+/// Implements the measuring methods of `Region`.
+/// `area` is the number of cells, `perimeter` is the number of unit edges on the boundary.
+/// `sides` is the number of straight fence segments, counted as the number of corners of the boundary.
+/// `holes` is the number of enclosed gaps, derived from the Euler characteristic `V - E + F = 1 - holes`.
+/// For example,
+/// ```
+/// use utils::{Grid, Point};
+///
+/// let grid = Grid::parse("AAA\nABA\nAAA");
+/// let ring = grid.region_at(Point::new(0, 0));
+/// assert_eq!(ring.area(), 8);
+/// assert_eq!(ring.perimeter, 16);
+/// assert_eq!(ring.sides, 8);
+/// assert_eq!(ring.holes, 1);
+/// assert_eq!((ring.min, ring.max), (Point::new(0, 0), Point::new(2, 2)));
+///
+/// let center = grid.region_at(Point::new(1, 1));
+/// assert_eq!((center.area(), center.perimeter, center.sides, center.holes), (1, 4, 4, 0));
+/// assert_eq!((center.width(), center.height()), (1, 1));
+/// ```
+impl Region {
+    #[inline]
+    #[must_use]
+    pub fn area(&self) -> u32 {
+        self.cells.len() as u32
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        (self.max.x - self.min.x + 1) as u32
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        (self.max.y - self.min.y + 1) as u32
+    }
+
+    /// Measures the given cells, `inside` must answer whether a point belongs to the same region.
+    fn measure(cells: Vec<Point>, inside: impl Fn(Point) -> bool) -> Self {
+        let mut perimeter = 0;
+        let mut sides = 0;
+        let mut vertices = 0;
+        let mut min = cells[0];
+        let mut max = cells[0];
+
+        for &cell in cells.iter() {
+            min = Point::new(min.x.min(cell.x), min.y.min(cell.y));
+            max = Point::new(max.x.max(cell.x), max.y.max(cell.y));
+
+            for dir in DIRECTIONS {
+                let side = dir.cw();
+                let a = inside(cell + dir);
+                let b = inside(cell + side);
+                let c = inside(cell + dir + side);
+
+                if !a {
+                    perimeter += 1;
+                }
+
+                // Convex corner, or concave corner that turns into the region
+                if (!a && !b) || (a && b && !c) {
+                    sides += 1;
+                }
+
+                // Every vertex is owned by the first (row-major) region cell touching it
+                let owner = [cell, cell + dir, cell + side, cell + dir + side]
+                    .into_iter()
+                    .filter(|&p| p == cell || inside(p))
+                    .min();
+                if owner == Some(cell) {
+                    vertices += 1;
+                }
+            }
+        }
+
+        let faces = cells.len() as i32;
+        let edges = (4 * faces + perimeter as i32) / 2;
+        let holes = (1 - vertices + edges - faces) as u32;
+
+        Region {
+            cells,
+            perimeter,
+            sides,
+            holes,
+            min,
+            max,
+        }
+    }
+}
+
+/// Implements the `regions` and `region_at` methods for `Grid`.
+/// `regions` splits the whole grid into regions in row-major order of their first cell.
+/// `region_at` measures only the region that contains the given point.
+/// For example,
+/// ```
+/// use utils::{Grid, Point};
+///
+/// let grid = Grid::parse("AAAA\nBBCD\nBBCC\nEEEC");
+/// let regions = grid.regions();
+/// assert_eq!(regions.len(), 5);
+///
+/// let price: u32 = regions.iter().map(|r| r.area() * r.perimeter).sum();
+/// assert_eq!(price, 140);
+///
+/// let discount: u32 = regions.iter().map(|r| r.area() * r.sides).sum();
+/// assert_eq!(discount, 80);
+///
+/// assert_eq!(grid.region_at(Point::new(2, 1)).cells.len(), 4);
+/// ```
+impl<T: Copy + PartialEq> Grid<T> {
+    #[must_use]
+    pub fn regions(&self) -> Vec<Region> {
+        let mut labels = self.clone_with(u32::MAX);
+        let mut regions = vec![];
+
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
+                let start = Point::new(x, y);
+                if labels[start] != u32::MAX {
+                    continue;
+                }
+
+                let label = regions.len() as u32;
+                let cells = self.flood(start, |p| {
+                    if labels[p] == u32::MAX {
+                        labels[p] = label;
+                        true
+                    } else {
+                        false
+                    }
+                });
+
+                regions.push(Region::measure(cells, |p| {
+                    self.contains(p) && labels[p] == label
+                }));
+            }
+        }
+
+        regions
+    }
+
+    #[must_use]
+    pub fn region_at(&self, start: Point) -> Region {
+        let mut seen = BitGrid::new(self.width, self.height);
+        let cells = self.flood(start, |p| seen.insert(p));
+        Region::measure(cells, |p| self.contains(p) && seen[p])
+    }
+
+    /// Collects every cell orthogonally connected to `start` with the same value.
+    /// `visit` marks a cell as taken and returns whether it was free before.
+    fn flood(&self, start: Point, mut visit: impl FnMut(Point) -> bool) -> Vec<Point> {
+        let value = self[start];
+        let mut cells = vec![];
+        let mut stack = vec![start];
+        visit(start);
+
+        while let Some(cell) = stack.pop() {
+            cells.push(cell);
+            for dir in DIRECTIONS {
+                let next = cell + dir;
+                if self.contains(next) && self[next] == value && visit(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        cells
+    }
+}
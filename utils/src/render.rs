@@ -0,0 +1,244 @@
+//! This module provides ways to look at a `Grid` while debugging.
+//!
+//! `Grid<u8>` implements `Display`, printing every row as a line of characters.
+//! `to_ansi` renders a grid for the terminal, with highlighted points drawn on a coloured background.
+//! `to_ppm` and `to_png` export a grid as an image where every cell becomes a single pixel.
+//! The PNG encoder is self-contained, it writes uncompressed deflate blocks so no external crate is needed.
+
+use crate::grid::Grid;
+use crate::point::Point;
+use std::fmt::{Display, Formatter, Write};
+use std::io;
+use std::path::Path;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(70, 180, 70);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(230, 190, 40);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+}
+
+/// Implements `Display` for `Grid<u8>`, printing each row on its own line.
+/// For example,
+/// ```
+/// use utils::Grid;
+///
+/// let grid = Grid::parse("#..\n.#.\n..#");
+/// assert_eq!(grid.to_string(), "#..\n.#.\n..#");
+/// ```
+impl Display for Grid<u8> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                f.write_char('\n')?;
+            }
+            for &cell in row {
+                f.write_char(cell as char)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Implements the terminal renderers for `Grid`.
+/// `to_ansi_with` maps every cell to a character and an optional foreground colour.
+/// Every `(colour, points)` pair in `highlights` paints the background of those points, later pairs win.
+/// `Grid<u8>::to_ansi` is the shorthand that prints the cells as they are.
+/// For example,
+/// ```
+/// use utils::{Grid, Point, Rgb};
+///
+/// let grid = Grid::parse("ab\ncd");
+/// let path = [Point::new(1, 0)];
+/// let text = grid.to_ansi(&[(Rgb::RED, &path)]);
+/// assert_eq!(text, "a\x1b[48;2;220;50;47mb\x1b[0m\ncd\n");
+/// ```
+impl<T> Grid<T> {
+    #[must_use]
+    pub fn to_ansi_with(
+        &self,
+        cell: impl Fn(&T) -> (char, Option<Rgb>),
+        highlights: &[(Rgb, &[Point])],
+    ) -> String {
+        let mut background = Grid::new(self.width, self.height, None);
+        for &(colour, points) in highlights {
+            for &point in points {
+                if self.contains(point) {
+                    background[point] = Some(colour);
+                }
+            }
+        }
+
+        let mut result = String::new();
+        for point in self.positions() {
+            let (c, foreground) = cell(&self[point]);
+            let back = background[point];
+
+            if let Some(Rgb(r, g, b)) = foreground {
+                let _ = write!(result, "\x1b[38;2;{r};{g};{b}m");
+            }
+            if let Some(Rgb(r, g, b)) = back {
+                let _ = write!(result, "\x1b[48;2;{r};{g};{b}m");
+            }
+            result.push(c);
+            if foreground.is_some() || back.is_some() {
+                result.push_str("\x1b[0m");
+            }
+            if point.x == self.width as i32 - 1 {
+                result.push('\n');
+            }
+        }
+
+        result
+    }
+}
+
+impl Grid<u8> {
+    #[must_use]
+    pub fn to_ansi(&self, highlights: &[(Rgb, &[Point])]) -> String {
+        self.to_ansi_with(|&c| (c as char, None), highlights)
+    }
+}
+
+/// Implements the image exporters for `Grid`.
+/// `colour` maps every cell to the colour of its pixel.
+/// `save_image` picks the format from the file extension, `.png` or `.ppm`.
+/// For example,
+/// ```
+/// use utils::{Grid, Rgb};
+///
+/// let grid = Grid::parse("#.\n.#");
+/// let colour = |&c: &u8| if c == b'#' { Rgb::GREEN } else { Rgb::BLACK };
+///
+/// let ppm = grid.to_ppm(colour);
+/// assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+/// assert_eq!(ppm.len(), 11 + 2 * 2 * 3);
+///
+/// let png = grid.to_png(colour);
+/// assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+/// assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+/// ```
+impl<T> Grid<T> {
+    #[must_use]
+    pub fn to_ppm(&self, colour: impl Fn(&T) -> Rgb) -> Vec<u8> {
+        let mut result = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        result.extend(self.pixels(colour));
+        result
+    }
+
+    #[must_use]
+    pub fn to_png(&self, colour: impl Fn(&T) -> Rgb) -> Vec<u8> {
+        let pixels = self.pixels(colour);
+
+        // Every scanline starts with filter type 0 (none)
+        let mut raw = Vec::with_capacity(pixels.len() + self.height as usize);
+        for row in pixels.chunks(self.width as usize * 3) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend(self.width.to_be_bytes());
+        header.extend(self.height.to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut result = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut result, b"IHDR", &header);
+        png_chunk(&mut result, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut result, b"IEND", &[]);
+        result
+    }
+
+    pub fn save_image(&self, path: impl AsRef<Path>, colour: impl Fn(&T) -> Rgb) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.to_png(colour),
+            Some("ppm") => self.to_ppm(colour),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Image path must end with .png or .ppm",
+                ))
+            }
+        };
+        std::fs::write(path, bytes)
+    }
+
+    fn pixels(&self, colour: impl Fn(&T) -> Rgb) -> Vec<u8> {
+        self.data
+            .iter()
+            .flat_map(|cell| {
+                let Rgb(r, g, b) = colour(cell);
+                [r, g, b]
+            })
+            .collect()
+    }
+}
+
+/* ------------------- PNG Helpers ------------------- */
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xEDB88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(u32::MAX, |crc, &b| {
+        CRC_TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// Wraps the data into a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none() as u8;
+        let len = block.len() as u16;
+        out.push(last);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
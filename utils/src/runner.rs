@@ -1,12 +1,14 @@
+use crate::input::normalize;
 use criterion::{black_box, Criterion};
 use std::fmt::Debug;
 use std::fs;
 use std::time::{Duration, Instant};
 
+/// Reads and normalizes the file, see `normalize`. Returns an empty string if the file does not exist.
 #[inline]
 pub fn read_file(path: &str) -> String {
     if fs::exists(path).expect("Could not check file") {
-        fs::read_to_string(path).expect("Could not read file")
+        normalize(&fs::read_to_string(path).expect("Could not read file"))
     } else {
         String::new()
     }
@@ -16,10 +18,9 @@ pub fn read_file(path: &str) -> String {
 /// In the case of benchmark call, we will be using the relative path!
 #[inline]
 pub fn get_input(opt_day: Option<u8>) -> String {
-    let result = if opt_day.is_some() {
-        read_file(&format!("day{}/src/input", opt_day.unwrap()))
-    } else {
-        read_file("./src/input")
+    let result = match opt_day {
+        Some(day) => read_file(&format!("day{day}/src/input")),
+        None => read_file("./src/input"),
     };
 
     if result.is_empty() {
@@ -31,10 +32,9 @@ pub fn get_input(opt_day: Option<u8>) -> String {
 
 #[inline]
 pub fn get_example(opt_day: Option<u8>) -> String {
-    let result = if opt_day.is_some() {
-        read_file(&format!("day{}/src/example", opt_day.unwrap()))
-    } else {
-        read_file("./src/example")
+    let result = match opt_day {
+        Some(day) => read_file(&format!("day{day}/src/example")),
+        None => read_file("./src/example"),
     };
 
     if result.is_empty() {
@@ -0,0 +1,217 @@
+//! This module provides a `SparseGrid` struct, a grid without fixed bounds keyed by `Point`.
+//!
+//! Cells are stored in an `AHashMap`, so only occupied points take memory and negative coordinates are fine.
+//! The grid keeps track of the bounding box of every point inserted into it, removing a point does not shrink it.
+//! It mirrors the `Grid` API where it makes sense and can be converted from and to a dense `Grid`.
+
+use crate::grid::Grid;
+use crate::point::{Point, DIRECTIONS};
+use ahash::AHashMap;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug, Default)]
+pub struct SparseGrid<T> {
+    cells: AHashMap<Point, T>,
+    bounds: Option<(Point, Point)>,
+}
+
+/// Implements the basic map methods for `SparseGrid`.
+/// `insert` grows the bounding box when needed and returns the previous value of the point.
+/// `bounds` returns the inclusive `(min, max)` corners of the bounding box, or `None` if nothing was ever inserted.
+/// For example,
+/// ```
+/// use utils::{Point, SparseGrid};
+///
+/// let mut grid = SparseGrid::new();
+/// assert_eq!(grid.bounds(), None);
+///
+/// grid.insert(Point::new(2, -1), b'#');
+/// assert_eq!(grid.insert(Point::new(-3, 4), b'.'), None);
+/// assert_eq!(grid.insert(Point::new(-3, 4), b'#'), Some(b'.'));
+///
+/// assert_eq!(grid.len(), 2);
+/// assert_eq!(grid[Point::new(2, -1)], b'#');
+/// assert_eq!(grid.get(Point::new(0, 0)), None);
+/// assert_eq!(grid.bounds(), Some((Point::new(-3, -1), Point::new(2, 4))));
+///
+/// assert_eq!(grid.remove(Point::new(2, -1)), Some(b'#'));
+/// assert!(!grid.contains(Point::new(2, -1)));
+/// ```
+impl<T> SparseGrid<T> {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: AHashMap::new(),
+            bounds: None,
+        }
+    }
+
+    #[inline]
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+            None => (point, point),
+        });
+        self.cells.insert(point, value)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// Yields the orthogonal neighbours of a point that are occupied, in `DIRECTIONS` order.
+    #[inline]
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS
+            .into_iter()
+            .map(move |dir| point + dir)
+            .filter(|next| self.cells.contains_key(next))
+    }
+}
+
+/// Implements the `find_all` method for `SparseGrid`.
+/// Just like `Grid::find_all`, the points are returned in row-major order.
+/// For example,
+/// ```
+/// use utils::{Point, SparseGrid};
+///
+/// let mut grid = SparseGrid::new();
+/// grid.insert(Point::new(5, 5), 1);
+/// grid.insert(Point::new(-5, 5), 1);
+/// grid.insert(Point::new(0, 0), 2);
+/// assert_eq!(grid.find_all(1), vec![Point::new(-5, 5), Point::new(5, 5)]);
+/// ```
+impl<T: PartialEq> SparseGrid<T> {
+    #[must_use]
+    pub fn find_all(&self, value: T) -> Vec<Point> {
+        let mut result: Vec<Point> = self
+            .iter()
+            .filter(|&(_, v)| *v == value)
+            .map(|(point, _)| point)
+            .collect();
+        result.sort_unstable();
+        result
+    }
+}
+
+/// Implements the conversions between `SparseGrid` and `Grid`.
+/// `from_grid` keeps only the cells of a dense grid that match the predicate.
+/// `to_grid` returns a dense grid covering the bounding box filled with `fill`, and the point its top-left corner maps to.
+/// For example,
+/// ```
+/// use utils::{Grid, Point, SparseGrid};
+///
+/// let dense = Grid::parse("..#\n#..");
+/// let mut sparse = SparseGrid::from_grid(&dense, |&c| c == b'#');
+/// assert_eq!(sparse.len(), 2);
+///
+/// sparse.insert(Point::new(-1, 0), b'@');
+/// let (grid, origin) = sparse.to_grid(b'.');
+/// assert_eq!(origin, Point::new(-1, 0));
+/// assert_eq!(grid.to_string(), "@..#\n.#..");
+/// assert_eq!(sparse.to_string(), "@..#\n.#..");
+/// ```
+impl<T: Copy> SparseGrid<T> {
+    #[must_use]
+    pub fn from_grid(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> Self {
+        let mut result = SparseGrid::new();
+        for point in grid.positions() {
+            if keep(&grid[point]) {
+                result.insert(point, grid[point]);
+            }
+        }
+        result
+    }
+
+    #[must_use]
+    pub fn to_grid(&self, fill: T) -> (Grid<T>, Point) {
+        let Some((min, max)) = self.bounds else {
+            return (Grid::new(0, 0, fill), Point::new(0, 0));
+        };
+
+        let size = max - min + 1;
+        let mut grid = Grid::new(size.x as u32, size.y as u32, fill);
+        for (point, &value) in self.iter() {
+            grid[point - min] = value;
+        }
+        (grid, min)
+    }
+}
+
+/// Implements `Display` for `SparseGrid<u8>`, empty cells inside the bounding box are printed as `.`.
+impl Display for SparseGrid<u8> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.to_grid(b'.').0.fmt(f)
+    }
+}
+
+/// Implements the `Index` and `IndexMut` traits for `SparseGrid`.
+/// Unlike `get`, indexing a point that has no value panics.
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        match self.cells.get(&index) {
+            Some(value) => value,
+            None => panic!("Point {index} has no value in the sparse grid"),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for SparseGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        match self.cells.get_mut(&index) {
+            Some(value) => value,
+            None => panic!("Point {index} has no value in the sparse grid"),
+        }
+    }
+}
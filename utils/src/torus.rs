@@ -0,0 +1,202 @@
+//! This module provides the wrap-around (toroidal) topology for grids and simulations.
+//!
+//! `TorusGrid` wraps a `Grid` so that indexing and neighbour iteration continue on the opposite edge.
+//! It dereferences to the inner `Grid`, so every other `Grid` method is still available.
+//! `Particles` moves a set of points with constant velocities on a torus, jumping any number of steps in closed form.
+
+use crate::grid::Grid;
+use crate::point::{Point, DIRECTIONS};
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+#[derive(Debug)]
+pub struct TorusGrid<T> {
+    pub grid: Grid<T>,
+}
+
+/// Implements the wrapping methods for `TorusGrid`.
+/// `wrap` moves any point into the grid, `neighbours` always yields four points since there are no edges.
+/// Indexing with a point outside the grid wraps instead of panicking.
+/// For example,
+/// ```
+/// use utils::{Grid, Point, TorusGrid};
+///
+/// let mut torus = Grid::parse("ab\ncd").into_torus();
+/// assert_eq!(torus[Point::new(-1, 0)], b'b');
+/// assert_eq!(torus[Point::new(2, 3)], b'c');
+///
+/// torus[Point::new(5, 5)] = b'x';
+/// assert_eq!(torus.grid.to_string(), "ab\ncx");
+///
+/// assert_eq!(torus.wrap(Point::new(-3, 2)), Point::new(1, 0));
+/// let around: Vec<Point> = torus.neighbours(Point::new(0, 0)).collect();
+/// assert_eq!(around, vec![Point::new(1, 0), Point::new(0, 1), Point::new(1, 0), Point::new(0, 1)]);
+///
+/// let empty = TorusGrid::new(3, 3, 0);
+/// assert_eq!(empty.into_grid().data.len(), 9);
+/// ```
+impl<T: Copy> TorusGrid<T> {
+    #[inline]
+    #[must_use]
+    pub fn new(width: u32, height: u32, fill_value: T) -> Self {
+        TorusGrid {
+            grid: Grid::new(width, height, fill_value),
+        }
+    }
+}
+
+impl<T> TorusGrid<T> {
+    #[inline]
+    #[must_use]
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn wrap(&self, point: Point) -> Point {
+        point.rem_euclid(Point::new(self.grid.width as i32, self.grid.height as i32))
+    }
+
+    #[inline]
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS
+            .into_iter()
+            .map(move |dir| self.wrap(point + dir))
+    }
+}
+
+impl<T> Grid<T> {
+    #[inline]
+    #[must_use]
+    pub fn into_torus(self) -> TorusGrid<T> {
+        TorusGrid { grid: self }
+    }
+}
+
+impl<T> Deref for TorusGrid<T> {
+    type Target = Grid<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.grid
+    }
+}
+
+impl<T> DerefMut for TorusGrid<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grid
+    }
+}
+
+impl<T> Index<Point> for TorusGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        &self.grid[self.wrap(index)]
+    }
+}
+
+impl<T> IndexMut<Point> for TorusGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        let index = self.wrap(index);
+        &mut self.grid[index]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Particles {
+    pub size: Point,
+    pub positions: Vec<Point>,
+    pub velocities: Vec<Point>,
+}
+
+/// Implements the simulation methods for `Particles`.
+/// `positions_after` computes `(position + velocity * steps) mod size` for every particle without stepping through time.
+/// `advance` does the same in place, `period` is the number of steps after which every state repeats.
+/// `to_torus` counts how many particles are on every cell.
+/// For example,
+/// ```
+/// use utils::{Particles, Point};
+///
+/// let mut robots = Particles::new(Point::new(11, 7));
+/// robots.push(Point::new(2, 4), Point::new(2, -3));
+///
+/// let after: Vec<Point> = robots.positions_after(5).collect();
+/// assert_eq!(after, vec![Point::new(1, 3)]);
+///
+/// robots.advance(1_000_000_000_000);
+/// assert_eq!(robots.positions[0], robots.positions_after(robots.period()).next().unwrap());
+/// assert_eq!(robots.period(), 77);
+/// assert_eq!(robots.to_torus()[robots.positions[0]], 1);
+/// ```
+impl Particles {
+    #[inline]
+    #[must_use]
+    pub fn new(size: Point) -> Self {
+        Particles {
+            size,
+            positions: vec![],
+            velocities: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, position: Point, velocity: Point) {
+        self.positions.push(position);
+        self.velocities.push(velocity);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn positions_after(&self, steps: u64) -> impl Iterator<Item = Point> + '_ {
+        // Reduce the steps per axis first, so the multiplication can never overflow
+        let size = self.size;
+        let steps_x = (steps % size.x as u64) as i64;
+        let steps_y = (steps % size.y as u64) as i64;
+
+        self.positions
+            .iter()
+            .zip(self.velocities.iter())
+            .map(move |(p, v)| {
+                let x = (p.x as i64 + v.x as i64 * steps_x).rem_euclid(size.x as i64);
+                let y = (p.y as i64 + v.y as i64 * steps_y).rem_euclid(size.y as i64);
+                Point::new(x as i32, y as i32)
+            })
+    }
+
+    pub fn advance(&mut self, steps: u64) {
+        self.positions = self.positions_after(steps).collect();
+    }
+
+    #[must_use]
+    pub fn period(&self) -> u64 {
+        let (width, height) = (self.size.x as u64, self.size.y as u64);
+        let (mut a, mut b) = (width, height);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        width / a * height
+    }
+
+    #[must_use]
+    pub fn to_torus(&self) -> TorusGrid<u32> {
+        let mut torus = TorusGrid::new(self.size.x as u32, self.size.y as u32, 0);
+        for &position in self.positions.iter() {
+            torus[position] += 1;
+        }
+        torus
+    }
+}
@@ -0,0 +1,133 @@
+//! This module provides the geometric transformations of a `Grid`.
+//!
+//! Every transformation returns a new `Grid`, the original one is left untouched.
+//! Rotations are clockwise, `rotate_90` followed by `rotate_270` gives back the original grid.
+
+use crate::grid::Grid;
+use crate::point::Point;
+
+/// Implements `transpose`, `rotate_90`, `rotate_180`, `rotate_270`, `flip_horizontal` and `flip_vertical` for `Grid`.
+/// `flip_horizontal` mirrors the grid left to right, `flip_vertical` mirrors it top to bottom.
+/// For example,
+/// ```
+/// use utils::Grid;
+///
+/// let grid = Grid::parse("abc\ndef");
+/// assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+/// assert_eq!(grid.rotate_90().to_string(), "da\neb\nfc");
+/// assert_eq!(grid.rotate_180().to_string(), "fed\ncba");
+/// assert_eq!(grid.rotate_270().to_string(), "cf\nbe\nad");
+/// assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+/// assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+/// ```
+impl<T: Copy> Grid<T> {
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |p| Point::new(p.y, p.x))
+    }
+
+    #[must_use]
+    pub fn rotate_90(&self) -> Self {
+        let last_row = self.height as i32 - 1;
+        self.remap(self.height, self.width, |p| Point::new(p.y, last_row - p.x))
+    }
+
+    #[must_use]
+    pub fn rotate_180(&self) -> Self {
+        let mut data = self.data.clone();
+        data.reverse();
+        Grid {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    #[must_use]
+    pub fn rotate_270(&self) -> Self {
+        let last_column = self.width as i32 - 1;
+        self.remap(self.height, self.width, |p| {
+            Point::new(last_column - p.y, p.x)
+        })
+    }
+
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let last_column = self.width as i32 - 1;
+        self.remap(self.width, self.height, |p| {
+            Point::new(last_column - p.x, p.y)
+        })
+    }
+
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        let last_row = self.height as i32 - 1;
+        self.remap(self.width, self.height, |p| Point::new(p.x, last_row - p.y))
+    }
+
+    /// Builds a `width` x `height` grid where every point takes the value at `source(point)` in `self`.
+    fn remap(&self, width: u32, height: u32, source: impl Fn(Point) -> Point) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                data.push(self[source(Point::new(x, y))]);
+            }
+        }
+
+        Grid {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+/// Implements the `subgrid` and `map_expand` methods for `Grid`.
+/// `subgrid` crops a `width` x `height` window starting at `origin`, or returns `None` if it does not fit.
+/// `map_expand` replaces every cell with a horizontal tile of `K` cells, making the grid `K` times wider.
+/// For example,
+/// ```
+/// use utils::{Grid, Point};
+///
+/// let grid = Grid::parse("#O@\n...");
+/// let window = grid.subgrid(Point::new(1, 0), 2, 2).unwrap();
+/// assert_eq!(window.to_string(), "O@\n..");
+/// assert!(grid.subgrid(Point::new(2, 0), 2, 2).is_none());
+///
+/// let wide = grid.map_expand(|c| match c {
+///     b'O' => *b"[]",
+///     b'@' => *b"@.",
+///     c => [c, c],
+/// });
+/// assert_eq!(wide.to_string(), "##[]@.\n......");
+/// ```
+impl<T: Copy> Grid<T> {
+    #[must_use]
+    pub fn subgrid(&self, origin: Point, width: u32, height: u32) -> Option<Self> {
+        let end = origin + Point::new(width as i32 - 1, height as i32 - 1);
+        if width == 0 || height == 0 || !self.contains(origin) || !self.contains(end) {
+            return None;
+        }
+
+        let mut data = Vec::with_capacity((width * height) as usize);
+        for y in origin.y..=end.y {
+            let start = self.index_of(Point::new(origin.x, y))?;
+            data.extend_from_slice(&self.data[start..start + width as usize]);
+        }
+
+        Some(Grid {
+            width,
+            height,
+            data,
+        })
+    }
+
+    #[must_use]
+    pub fn map_expand<U: Copy, const K: usize>(&self, tile: impl Fn(T) -> [U; K]) -> Grid<U> {
+        Grid {
+            width: self.width * K as u32,
+            height: self.height,
+            data: self.data.iter().flat_map(|&cell| tile(cell)).collect(),
+        }
+    }
+}